@@ -0,0 +1,118 @@
+extern crate stable_mir as smir;
+
+use crate::domains::booleans::AbstractBool;
+use serde::Serialize;
+use std::fmt;
+
+/// Kinds of runtime errors (panics) that the analysis looks for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlarmKind {
+    /// An arithmetic operation (e.g. `Add`) may overflow the type of its operands.
+    Overflow(smir::mir::BinOp),
+    /// Negating the minimum value of a signed integer type.
+    OverflowNeg,
+    /// Dividing by zero.
+    DivisionByZero,
+    /// Taking the remainder of a division by zero.
+    RemainderByZero,
+    /// Indexing an array or slice out of its bounds.
+    BoundsCheck,
+}
+
+/// Whether a check is known to fail whenever it is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Verdict {
+    /// The check fails for some, but not necessarily all, executions that reach it.
+    MayFail,
+    /// The check fails every time it is reached.
+    Fails,
+}
+
+impl Verdict {
+    /// Classifies a check from the abstract value of its failure condition. Returns None if the
+    /// check can never fail.
+    pub(crate) fn from_failure(fails: AbstractBool) -> Option<Self> {
+        match fails {
+            AbstractBool::True => Some(Verdict::Fails),
+            AbstractBool::Top => Some(Verdict::MayFail),
+            AbstractBool::False | AbstractBool::Bot => None,
+        }
+    }
+}
+
+/// Whether a call to a panic function can be reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Reachability {
+    /// The call is proven to never be reached, e.g. an `assert!` that always holds.
+    Unreachable,
+    /// The call is reached by every execution of the function. The path from the entry to the
+    /// call only goes through branches whose outcome is known and checks that are proven to hold,
+    /// and doesn't call any other function.
+    Reachable,
+    /// The call may or may not be reached.
+    Unknown,
+}
+
+/// A call to a panic function, e.g. from an explicit `panic!` or a failing `assert!`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanicSite {
+    /// Name of the panic function, e.g. `core::panicking::panic`.
+    pub callee: String,
+    pub reachability: Reachability,
+    pub span: smir::ty::Span,
+}
+
+impl PanicSite {
+    /// Rule id of panic sites in SARIF logs, shared by every reachability.
+    pub const RULE_ID: &'static str = "explicit-panic";
+}
+
+/// A potential runtime error found by the analysis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alarm {
+    pub kind: AlarmKind,
+    pub verdict: Verdict,
+    pub span: smir::ty::Span,
+}
+
+impl AlarmKind {
+    /// Stable identifier of the kind of check, e.g. for the rules of a SARIF report.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            AlarmKind::Overflow(_) => "arithmetic-overflow",
+            AlarmKind::OverflowNeg => "negation-overflow",
+            AlarmKind::DivisionByZero => "division-by-zero",
+            AlarmKind::RemainderByZero => "remainder-by-zero",
+            AlarmKind::BoundsCheck => "index-out-of-bounds",
+        }
+    }
+}
+
+impl fmt::Display for AlarmKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlarmKind::Overflow(op) => write!(f, "arithmetic overflow in {op:?}"),
+            AlarmKind::OverflowNeg => write!(f, "arithmetic overflow in negation"),
+            AlarmKind::DivisionByZero => write!(f, "division by zero"),
+            AlarmKind::RemainderByZero => write!(f, "remainder with a divisor of zero"),
+            AlarmKind::BoundsCheck => write!(f, "index out of bounds"),
+        }
+    }
+}
+
+impl Alarm {
+    /// Source location of the alarm as `file:line:col`.
+    pub fn location(&self) -> String {
+        let lines = self.span.get_lines();
+        format!("{}:{}:{}", self.span.get_filename(), lines.start_line, lines.start_col)
+    }
+}
+
+impl fmt::Display for Alarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.verdict {
+            Verdict::MayFail => write!(f, "possible {} at {}", self.kind, self.location()),
+            Verdict::Fails => write!(f, "{} at {}", self.kind, self.location()),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Command line driver that runs the analysis on a crate, e.g.
+//!
+//! ```text
+//! absint src/lib.rs --crate-type=lib --edition=2021
+//! ```
+//!
+//! All arguments are passed on to rustc, except for `--absint-output=<format>` which selects how
+//! the results are reported: `diagnostics` (the default), `json`, `sarif`, or `tests` to print
+//! `#[test]` functions with boundary inputs for each function. With `--absint-corpus=<dir>`, the
+//! boundary inputs are also written to `dir` as libFuzzer seed corpora, one directory per
+//! function. `--absint-contexts=<k>` sets how many call contexts are analyzed per function
+//! (4 by default), and can also be given through the `ABSINT_CONTEXT_LIMIT` environment variable.
+//! The process exits with status 1 if the analysis found alarms, and with status 2 if
+//! the crate couldn't be compiled.
+//!
+//! The driver also works as a `RUSTC_WORKSPACE_WRAPPER`, which is how `cargo absint` runs it on
+//! every crate of a workspace. `cargo absint` enables that mode by setting `ABSINT_RESULTS_DIR`,
+//! and cargo then passes the path of rustc as first argument, whatever the binary is called (e.g.
+//! a rustup proxy or a renamed rustc). In that mode compilation continues after the analysis so that dependent crates can be built, and the alarms
+//! are written to the results directory instead of failing the build.
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+// The rustc_smir::run macro currently expects stable_mir to be in the namespace
+extern crate stable_mir;
+
+use abstract_interpretation_testing::corpus::write_corpus;
+use abstract_interpretation_testing::interpreter::{analyze_crate_with, get_sysroot, report_analysis, DEFAULT_CONTEXT_LIMIT};
+use abstract_interpretation_testing::report::OutputFormat;
+use abstract_interpretation_testing::results::{results_path, write_results, CONTEXT_LIMIT_ENV, RESULTS_DIR_ENV};
+use rustc_middle::ty::TyCtxt;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::sync::OnceLock;
+
+/// Where `record_results` writes the alarms of the crate being compiled.
+static RESULTS_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// How `analyze_and_report` reports the results.
+static OUTPUT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Where `analyze_and_report` writes the fuzzing corpora, if anywhere.
+static CORPUS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Number of alarms found by `analyze_and_report`, set once the analysis has run.
+static ALARM_COUNT: OnceLock<usize> = OnceLock::new();
+
+/// How many call contexts are analyzed per function.
+static CONTEXT_LIMIT: OnceLock<usize> = OnceLock::new();
+
+const OUTPUT_FLAG: &str = "--absint-output=";
+const CORPUS_FLAG: &str = "--absint-corpus=";
+const CONTEXTS_FLAG: &str = "--absint-contexts=";
+
+fn main() -> ExitCode {
+    // The first argument is the name of the binary, which rustc skips just like its own.
+    let mut rustc_args: Vec<String> = std::env::args().collect();
+    if rustc_args.len() < 2 {
+        eprintln!("usage: absint <crate root> [rustc flags...]");
+        return ExitCode::from(2);
+    }
+
+    let results_dir = std::env::var_os(RESULTS_DIR_ENV);
+    // The results directory is only set for the wrapper, so the first argument is rustc rather
+    // than a crate root, even if it isn't called `rustc`.
+    if results_dir.is_some() {
+        let rustc = rustc_args.remove(1);
+        // Cargo also queries the compiler (e.g. `rustc -vV`) and builds build scripts through the
+        // wrapper. Those are handed to the real rustc untouched.
+        if is_query(&rustc_args) || crate_name(&rustc_args) == Some("build_script_build") {
+            return match Command::new(rustc).args(&rustc_args[1..]).status() {
+                Ok(status) if status.success() => ExitCode::SUCCESS,
+                _ => ExitCode::FAILURE,
+            };
+        }
+    }
+
+    if let Err(e) = take_absint_flags(&mut rustc_args) {
+        eprintln!("error: {e}");
+        return ExitCode::from(2);
+    }
+
+    if !rustc_args.iter().any(|arg| arg.starts_with("--sysroot")) {
+        rustc_args.push(format!("--sysroot={}", get_sysroot()));
+    }
+
+    if let Some(results_dir) = results_dir {
+        let unit_name = format!("{}{}", crate_name(&rustc_args).unwrap_or("unknown"), extra_filename(&rustc_args).unwrap_or(""));
+        RESULTS_FILE.set(results_path(Path::new(&results_dir), &unit_name)).unwrap();
+        return match run!(rustc_args, record_results) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(_) => ExitCode::FAILURE,
+        };
+    }
+
+    // Checks that always fail are reported as errors, after which rustc ends the session as a
+    // failed compilation. Whether the analysis ran is what tells that apart from a crate that
+    // doesn't compile.
+    match (run!(rustc_args, analyze_and_report), ALARM_COUNT.get()) {
+        (_, Some(&alarm_count)) if alarm_count > 0 => {
+            eprintln!("error: analysis found {alarm_count} alarm(s)");
+            ExitCode::from(1)
+        }
+        (Ok(()), _) => ExitCode::SUCCESS,
+        (Err(e), _) => {
+            eprintln!("error: analysis did not run: {e:?}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Removes the flags of the driver from the arguments, so that they aren't passed on to rustc, and
+/// stores their values.
+fn take_absint_flags(args: &mut Vec<String>) -> Result<(), String> {
+    if let Some(format) = take_flag(args, OUTPUT_FLAG)? {
+        OUTPUT_FORMAT.set(format.parse()?).unwrap();
+    }
+    if let Some(dir) = take_flag(args, CORPUS_FLAG)? {
+        CORPUS_DIR.set(PathBuf::from(dir)).unwrap();
+    }
+    if let Some(limit) = take_flag(args, CONTEXTS_FLAG)?.or_else(|| std::env::var(CONTEXT_LIMIT_ENV).ok()) {
+        let limit = limit.parse().map_err(|_| format!("invalid number of call contexts `{limit}`"))?;
+        CONTEXT_LIMIT.set(limit).unwrap();
+    }
+    Ok(())
+}
+
+/// Removes every occurrence of the flag from the arguments and returns its value. Fails if the
+/// flag is given more than once.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let mut values: Vec<String> = Vec::new();
+    args.retain(|arg| match arg.strip_prefix(flag) {
+        Some(value) => {
+            values.push(value.to_string());
+            false
+        }
+        None => true,
+    });
+    if values.len() > 1 {
+        return Err(format!("`{flag}<value>` can only be given once"));
+    }
+    Ok(values.pop())
+}
+
+fn analyze_and_report(tcx: TyCtxt) -> ControlFlow<usize> {
+    let analysis = analyze_crate_with(context_limit());
+    if let Some(dir) = CORPUS_DIR.get() {
+        match write_corpus(dir, &analysis.tests) {
+            Ok(count) => eprintln!("wrote {count} seed input(s) to {}", dir.display()),
+            Err(e) => eprintln!("warning: could not write the fuzzing corpus to {}: {e}", dir.display()),
+        }
+    }
+    ALARM_COUNT.set(analysis.alarm_count()).unwrap();
+    report_analysis(tcx, &analysis, OUTPUT_FORMAT.get().copied().unwrap_or(OutputFormat::Diagnostics))
+}
+
+/// Analyzes the crate and writes its alarms to the results file. Always continues compilation since
+/// other crates of the workspace may depend on this one.
+fn record_results(_tcx: TyCtxt) -> ControlFlow<()> {
+    let analysis = analyze_crate_with(context_limit());
+    if let Some(path) = RESULTS_FILE.get() {
+        if let Err(e) = write_results(path, &analysis) {
+            eprintln!("warning: could not write analysis results to {}: {e}", path.display());
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+fn context_limit() -> usize {
+    CONTEXT_LIMIT.get().copied().unwrap_or(DEFAULT_CONTEXT_LIMIT)
+}
+
+/// Returns true if rustc is only asked for information rather than to compile a crate.
+fn is_query(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "-vV" || arg == "-V" || arg == "--version" || arg.starts_with("--print"))
+}
+
+fn crate_name(args: &[String]) -> Option<&str> {
+    args.windows(2)
+        .find(|pair| pair[0] == "--crate-name")
+        .map(|pair| pair[1].as_str())
+}
+
+fn extra_filename(args: &[String]) -> Option<&str> {
+    args.windows(2)
+        .find_map(|pair| match pair[0].as_str() {
+            "-C" => pair[1].strip_prefix("extra-filename="),
+            arg => arg.strip_prefix("-Cextra-filename="),
+        })
+}
@@ -0,0 +1,143 @@
+//! Cargo subcommand that runs the analysis on every crate of a workspace:
+//!
+//! ```text
+//! cargo absint [--absint-contexts=<k>] [cargo check flags...]
+//! ```
+//!
+//! `--absint-contexts=<k>` sets how many call contexts the analysis keeps per function.
+//!
+//! The packages are checked with the `absint` driver as `RUSTC_WORKSPACE_WRAPPER`, so dependencies
+//! are compiled normally and only workspace crates are analyzed. The alarms of all crates and
+//! targets are reported together once cargo finishes. The process exits with status 1 if there are
+//! alarms, and with status 2 if the workspace couldn't be checked.
+
+use abstract_interpretation_testing::results::{read_results, results_path, CONTEXT_LIMIT_ENV, RESULTS_DIR_ENV};
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Command, ExitCode, Stdio};
+
+const CONTEXTS_FLAG: &str = "--absint-contexts=";
+
+fn main() -> ExitCode {
+    // Cargo runs `cargo absint <args>` as `cargo-absint absint <args>`.
+    let mut cargo_args: Vec<String> = std::env::args()
+        .skip(1)
+        .enumerate()
+        .filter(|(i, arg)| !(*i == 0 && arg == "absint"))
+        .map(|(_, arg)| arg)
+        .collect();
+    // The driver can't be given flags through cargo, so the context limit is passed on in the
+    // environment.
+    let mut limits: Vec<String> = Vec::new();
+    cargo_args.retain(|arg| match arg.strip_prefix(CONTEXTS_FLAG) {
+        Some(limit) => {
+            limits.push(limit.to_string());
+            false
+        }
+        None => true,
+    });
+    if limits.len() > 1 {
+        eprintln!("error: `{CONTEXTS_FLAG}<k>` can only be given once");
+        return ExitCode::from(2);
+    }
+    let context_limit = limits.pop();
+    if context_limit.as_ref().is_some_and(|limit| limit.parse::<usize>().is_err()) {
+        eprintln!("error: invalid number of call contexts `{}`", context_limit.unwrap());
+        return ExitCode::from(2);
+    }
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let driver = match std::env::current_exe() {
+        Ok(exe) => exe.with_file_name(format!("absint{}", std::env::consts::EXE_SUFFIX)),
+        Err(e) => {
+            eprintln!("error: could not locate the absint driver: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let results_dir = match target_directory(&cargo) {
+        Some(target_dir) => target_dir.join("absint"),
+        None => {
+            eprintln!("error: could not determine the target directory of the workspace");
+            return ExitCode::from(2);
+        }
+    };
+    if let Err(e) = std::fs::create_dir_all(&results_dir) {
+        eprintln!("error: could not create {}: {e}", results_dir.display());
+        return ExitCode::from(2);
+    }
+
+    // Results files are kept between runs: cargo doesn't recompile (and so doesn't re-analyze)
+    // crates that haven't changed, but it still reports their artifacts, which name the results
+    // files that are current.
+    let mut child = match Command::new(&cargo)
+        .arg("check")
+        .arg("--message-format=json-render-diagnostics")
+        .args(&cargo_args)
+        .env("RUSTC_WORKSPACE_WRAPPER", &driver)
+        .env(RESULTS_DIR_ENV, &results_dir)
+        .envs(context_limit.map(|limit| (CONTEXT_LIMIT_ENV, limit)))
+        .stdout(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("error: could not run cargo: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut seen = HashSet::new();
+    let mut alarm_count = 0;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        // The compiler's own output is forwarded as is, anything else is a cargo message.
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(&line) else { continue };
+        if message["reason"] != "compiler-artifact" {
+            continue;
+        }
+        let target = &message["target"];
+        let filenames = message["filenames"].as_array().into_iter().flatten().filter_map(|f| f.as_str());
+        for filename in filenames {
+            let Some(unit_name) = unit_name(filename) else { continue };
+            if !seen.insert(unit_name.clone()) {
+                continue;
+            }
+            let Some(alarms) = read_results(&results_path(&results_dir, &unit_name)) else { continue };
+            let kinds: Vec<&str> = target["kind"].as_array().into_iter().flatten().filter_map(|k| k.as_str()).collect();
+            let test = if message["profile"]["test"] == true { " test" } else { "" };
+            for alarm in &alarms {
+                println!("warning: {} ({}{}): {alarm}", target["name"].as_str().unwrap_or("?"), kinds.join(", "), test);
+            }
+            alarm_count += alarms.len();
+        }
+    }
+
+    let checked = matches!(child.wait(), Ok(status) if status.success());
+    if !checked {
+        ExitCode::from(2)
+    } else if alarm_count > 0 {
+        eprintln!("error: analysis found {alarm_count} alarm(s)");
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Returns the target directory of the workspace, as reported by `cargo metadata`.
+fn target_directory(cargo: &str) -> Option<PathBuf> {
+    let output = Command::new(cargo)
+        .args(["metadata", "--no-deps", "--format-version=1"])
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    metadata["target_directory"].as_str().map(PathBuf::from)
+}
+
+/// Name of the compilation unit that produced an artifact, e.g. `foo-1a2b3c` for
+/// `target/debug/deps/libfoo-1a2b3c.rmeta`.
+fn unit_name(artifact: &str) -> Option<String> {
+    let stem = PathBuf::from(artifact).file_stem()?.to_str()?.to_string();
+    Some(stem.strip_prefix("lib").map(str::to_string).unwrap_or(stem))
+}
@@ -0,0 +1,135 @@
+extern crate stable_mir as smir;
+
+use smir::mir::{BinOp, Local, Operand, Place};
+use std::collections::HashMap;
+
+/// Comparison operators that can be used to refine the abstract values of their operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    pub(crate) fn from_binop(op: &BinOp) -> Option<Self> {
+        match op {
+            BinOp::Lt => Some(Comparison::Lt),
+            BinOp::Le => Some(Comparison::Le),
+            BinOp::Gt => Some(Comparison::Gt),
+            BinOp::Ge => Some(Comparison::Ge),
+            BinOp::Eq => Some(Comparison::Eq),
+            BinOp::Ne => Some(Comparison::Ne),
+            _ => None,
+        }
+    }
+
+    /// The comparison that holds whenever this one doesn't, e.g. `>=` for `<`.
+    pub(crate) fn negate(self) -> Self {
+        match self {
+            Comparison::Lt => Comparison::Ge,
+            Comparison::Le => Comparison::Gt,
+            Comparison::Gt => Comparison::Le,
+            Comparison::Ge => Comparison::Lt,
+            Comparison::Eq => Comparison::Ne,
+            Comparison::Ne => Comparison::Eq,
+        }
+    }
+
+    /// The equivalent comparison with the operands swapped, e.g. `>` for `<`.
+    pub(crate) fn flip(self) -> Self {
+        match self {
+            Comparison::Lt => Comparison::Gt,
+            Comparison::Le => Comparison::Ge,
+            Comparison::Gt => Comparison::Lt,
+            Comparison::Ge => Comparison::Le,
+            Comparison::Eq => Comparison::Eq,
+            Comparison::Ne => Comparison::Ne,
+        }
+    }
+}
+
+/// A comparison whose boolean result was stored in a local.
+#[derive(Debug, Clone)]
+pub(crate) struct Condition {
+    pub(crate) comparison: Comparison,
+    pub(crate) left: Operand,
+    pub(crate) right: Operand,
+}
+
+/// Remembers which locals hold the result of a comparison, and which locals are plain copies of
+/// other locals, within a single basic block. This lets a `SwitchInt` or `Assert` on a boolean
+/// local refine the values that were compared to produce it.
+///
+/// Entries are invalidated as soon as any local they mention is reassigned, so a tracked condition
+/// always describes the current values of its operands.
+#[derive(Debug, Default)]
+pub(crate) struct ConditionTracker {
+    conditions: HashMap<Local, Condition>,
+    copies: HashMap<Local, Local>,
+}
+
+impl ConditionTracker {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the condition currently held by the local, if any.
+    pub(crate) fn condition(&self, local: Local) -> Option<&Condition> {
+        self.conditions.get(&local)
+    }
+
+    /// Returns the local that `local` is currently a copy of, if any.
+    pub(crate) fn copy_source(&self, local: Local) -> Option<Local> {
+        self.copies.get(&local).cloned()
+    }
+
+    /// Updates the tracked conditions and copies after an assignment of `rvalue` to `place`.
+    pub(crate) fn assign(&mut self, place: &Place, rvalue: &smir::mir::Rvalue) {
+        // A write through a reference may modify any mutably borrowed local, so nothing tracked so
+        // far can be relied upon anymore.
+        if place.projection.iter().any(|proj| matches!(proj, smir::mir::ProjectionElem::Deref)) {
+            self.conditions.clear();
+            self.copies.clear();
+            return;
+        }
+
+        self.invalidate(place.local);
+        if !place.projection.is_empty() {
+            return;
+        }
+
+        match rvalue {
+            smir::mir::Rvalue::BinaryOp(op, left, right) => {
+                if let Some(comparison) = Comparison::from_binop(op) {
+                    self.conditions.insert(place.local, Condition {
+                        comparison,
+                        left: left.clone(),
+                        right: right.clone(),
+                    });
+                }
+            }
+            smir::mir::Rvalue::Use(Operand::Copy(source)) | smir::mir::Rvalue::Use(Operand::Move(source)) => {
+                if source.projection.is_empty() && source.local != place.local {
+                    self.copies.insert(place.local, source.local);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Forgets every condition and copy that mentions the local, e.g. because it was reassigned.
+    pub(crate) fn invalidate(&mut self, local: Local) {
+        let mentions = |op: &Operand| match op {
+            Operand::Copy(place) | Operand::Move(place) => place.local == local,
+            Operand::Constant(_) => false,
+        };
+        self.conditions.retain(|target, cond| {
+            *target != local && !mentions(&cond.left) && !mentions(&cond.right)
+        });
+        self.copies.retain(|target, source| *target != local && *source != local);
+    }
+}
@@ -0,0 +1,97 @@
+//! Export of the boundary inputs found by `testgen` as seed corpora for libFuzzer (e.g. through
+//! cargo-fuzz). Inputs that satisfy the interval constraints of a branch, like `x == 0xDEAD`, are
+//! exactly the ones that random mutation rarely finds.
+//!
+//! Every function gets its own corpus directory, named after the function with `::` replaced by
+//! `_`, holding one file per input. A file contains the arguments in order, each encoded as the
+//! little-endian bytes of its type (e.g. 4 bytes for a `u32`) and booleans as a single byte. Fuzz
+//! targets decode them the same way, e.g. with `u32::from_le_bytes`.
+
+extern crate stable_mir as smir;
+
+use crate::domains::pointer_width;
+use crate::testgen::{Argument, ArgumentType, ConcreteValue, TestCase};
+use smir::ty::{IntTy, UintTy};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Writes a corpus directory per function into `dir`. Returns the number of seed files written.
+/// Has to be called from within the callback of `run!` since the width of `usize` depends on the
+/// compilation target.
+pub fn write_corpus(dir: &Path, tests: &HashMap<String, Vec<TestCase>>) -> io::Result<usize> {
+    let mut written = 0;
+    for (function, cases) in tests {
+        if cases.is_empty() {
+            continue;
+        }
+        let function_dir = dir.join(function.replace("::", "_"));
+        fs::create_dir_all(&function_dir)?;
+        for (i, case) in cases.iter().enumerate() {
+            let name = match case.branch {
+                Some(branch) => format!("seed-{i}-bb{}-bb{}", branch.block, branch.target),
+                None => format!("seed-{i}-entry"),
+            };
+            fs::write(function_dir.join(name), encode(&case.arguments))?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+/// Concatenates the little-endian encodings of the arguments.
+pub(crate) fn encode(arguments: &[Argument]) -> Vec<u8> {
+    arguments.iter()
+        .flat_map(|argument| {
+            let bytes = match argument.value {
+                ConcreteValue::Bool(b) => return vec![b as u8],
+                ConcreteValue::Int(i) => i.to_le_bytes(),
+                ConcreteValue::Uint(u) => u.to_le_bytes(),
+            };
+            // Two's complement truncation keeps the value of integers that fit in the type
+            bytes[..byte_width(argument.ty)].to_vec()
+        })
+        .collect()
+}
+
+/// Size in bytes of the encoding of a type, e.g. 4 for `i32`.
+fn byte_width(ty: ArgumentType) -> usize {
+    match ty {
+        ArgumentType::Bool | ArgumentType::Int(IntTy::I8) | ArgumentType::Uint(UintTy::U8) => 1,
+        ArgumentType::Int(IntTy::I16) | ArgumentType::Uint(UintTy::U16) => 2,
+        ArgumentType::Int(IntTy::I32) | ArgumentType::Uint(UintTy::U32) => 4,
+        ArgumentType::Int(IntTy::I64) | ArgumentType::Uint(UintTy::U64) => 8,
+        ArgumentType::Int(IntTy::I128) | ArgumentType::Uint(UintTy::U128) => 16,
+        ArgumentType::Int(IntTy::Isize) | ArgumentType::Uint(UintTy::Usize) => pointer_width() as usize / 8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_are_encoded_little_endian() {
+        let arguments = vec![
+            Argument { ty: ArgumentType::Uint(UintTy::U16), value: ConcreteValue::Uint(0xDEAD) },
+            Argument { ty: ArgumentType::Int(IntTy::I8), value: ConcreteValue::Int(-2) },
+            Argument { ty: ArgumentType::Bool, value: ConcreteValue::Bool(true) },
+            Argument { ty: ArgumentType::Int(IntTy::I32), value: ConcreteValue::Int(-1) },
+        ];
+        assert_eq!(encode(&arguments), vec![0xAD, 0xDE, 0xFE, 0x01, 0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn pointer_sized_integers_use_the_pointer_width() {
+        let arguments = vec![
+            Argument { ty: ArgumentType::Uint(UintTy::Usize), value: ConcreteValue::Uint(1) },
+            Argument { ty: ArgumentType::Int(IntTy::Isize), value: ConcreteValue::Int(-1) },
+        ];
+        let width = pointer_width() as usize / 8;
+        let mut expected = vec![0; width];
+        expected[0] = 1;
+        expected.extend(vec![0xFF; width]);
+        assert_eq!(encode(&arguments), expected);
+    }
+}
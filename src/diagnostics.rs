@@ -0,0 +1,39 @@
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use crate::alarms::{Alarm, Verdict};
+use crate::lints::Lint;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+
+/// Reports an alarm through rustc's diagnostics, so it is rendered with the source snippet and
+/// location like any compiler diagnostic. Checks that fail every time they are reached are
+/// errors, checks that may fail are warnings.
+///
+/// Has to be called from within the callback of `run!` since the span is converted back to an
+/// internal rustc span.
+pub(crate) fn emit_alarm(tcx: TyCtxt<'_>, function: &str, alarm: &Alarm) {
+    let span = rustc_internal::internal(alarm.span);
+    let note = format!("in function `{function}`");
+    match alarm.verdict {
+        Verdict::Fails => {
+            tcx.sess.struct_span_err(span, format!("this operation will panic at runtime: {}", alarm.kind))
+                .note(note)
+                .note("this check fails every time it is reached, so the program panics whenever it gets here")
+                .emit();
+        }
+        Verdict::MayFail => {
+            tcx.sess.struct_span_warn(span, format!("possible {}", alarm.kind))
+                .note(note)
+                .emit();
+        }
+    }
+}
+
+/// Reports a lint as a warning. Has to be called from within the callback of `run!`, like
+/// `emit_alarm`.
+pub(crate) fn emit_lint(tcx: TyCtxt<'_>, function: &str, lint: &Lint) {
+    tcx.sess.struct_span_warn(rustc_internal::internal(lint.span), lint.kind.to_string())
+        .note(format!("in function `{function}`"))
+        .emit();
+}
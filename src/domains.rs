@@ -7,5 +7,10 @@ mod value;
 pub(crate) mod booleans;
 pub(crate) mod interval;
 
-pub use function::AbstractFunction;
-pub use value::AbstractValue;
+#[cfg(test)]
+pub(crate) mod laws;
+
+pub use domain::AbstractDomain;
+pub use function::{AbstractFunction, CallContext};
+pub use value::{AbstractValue, OverflowMode};
+pub(crate) use value::{array_length, int_bounds, int_from_bits, length_bounds, pointer_width, uint_bounds};
@@ -55,6 +55,19 @@ impl AbstractDomain for AbstractBool {
         }
     }
 
+    fn meet(&self, other: &Self) -> Self {
+        match self.partial_cmp(other) {
+            None => AbstractBool::Bot, // Only can't compare True and False, so Bot is the meet
+            Some(Ordering::Equal) => *self,
+            Some(Ordering::Less) => *self,
+            Some(Ordering::Greater) => *other,
+        }
+    }
+
+    fn leq(&self, other: &Self) -> bool {
+        matches!(self.partial_cmp(other), Some(Ordering::Less | Ordering::Equal))
+    }
+
     fn widen(&self, other: &Self) -> Self {
         match (self, other) {
             (AbstractBool::Top, _) => AbstractBool::Top,
@@ -71,9 +84,33 @@ impl AbstractDomain for AbstractBool {
         }
     }
 
+    /// The lattice has a finite height, so narrowing can simply use the meet.
+    fn narrow(&self, other: &Self) -> Self {
+        self.meet(other)
+    }
+
     fn top(&self) -> Self {
         AbstractBool::Top
     }
+
+    fn bottom(&self) -> Self {
+        AbstractBool::Bot
+    }
+
+    fn is_bottom(&self) -> bool {
+        *self == AbstractBool::Bot
+    }
+}
+
+impl std::fmt::Display for AbstractBool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbstractBool::Top => write!(f, "top"),
+            AbstractBool::True => write!(f, "true"),
+            AbstractBool::False => write!(f, "false"),
+            AbstractBool::Bot => write!(f, "bottom"),
+        }
+    }
 }
 
 impl From<bool> for AbstractBool {
@@ -105,11 +142,40 @@ impl From<&smir::ty::Const> for AbstractBool {
 }
 
 impl AbstractBool {
+    /// Abstract boolean negation
+    pub(crate) fn not(&self) -> AbstractBool {
+        match self {
+            AbstractBool::True => AbstractBool::False,
+            AbstractBool::False => AbstractBool::True,
+            other => *other,
+        }
+    }
+
+    /// Abstract boolean conjunction
+    pub(crate) fn and(&self, other: &Self) -> AbstractBool {
+        match (self, other) {
+            (AbstractBool::Bot, _) | (_, AbstractBool::Bot) => AbstractBool::Bot,
+            (AbstractBool::False, _) | (_, AbstractBool::False) => AbstractBool::False,
+            (AbstractBool::True, AbstractBool::True) => AbstractBool::True,
+            _ => AbstractBool::Top,
+        }
+    }
+
+    /// Abstract boolean disjunction
+    pub(crate) fn or(&self, other: &Self) -> AbstractBool {
+        self.not().and(&other.not()).not()
+    }
+
+    /// Abstract boolean exclusive or
+    pub(crate) fn xor(&self, other: &Self) -> AbstractBool {
+        self.equals(other).not()
+    }
+
     /// Abstract boolean equality operation
     pub(crate) fn equals(&self, other: &Self) -> AbstractBool {
         match (self, other) {
-            (AbstractBool::Top, _) | (_, AbstractBool::Top) => AbstractBool::Top,
             (AbstractBool::Bot, _) | (_, AbstractBool::Bot) => AbstractBool::Bot,
+            (AbstractBool::Top, _) | (_, AbstractBool::Top) => AbstractBool::Top,
             (a, b) => {
                 if a == b {
                     AbstractBool::True
@@ -8,7 +8,19 @@ pub trait AbstractDomain {
     // fn abstraction(concrete: T) -> Self;
 
     fn join(&self, other: &Self) -> Self;
+    /// Greatest lower bound, i.e. the largest element that is below both elements.
+    fn meet(&self, other: &Self) -> Self;
+    /// Partial order of the lattice. Returns true if `self` abstracts a subset of the concrete
+    /// values that `other` abstracts.
+    fn leq(&self, other: &Self) -> bool;
     fn widen(&self, other: &Self) -> Self;
+    /// Narrowing operator, used after widening to recover precision. `other` is expected to be
+    /// below `self`, and the result lies between the two.
+    fn narrow(&self, other: &Self) -> Self;
     /// Get the top element in the lattice. For booleans, this is Top. For intervals, this is [min,max].
     fn top(&self) -> Self;
+    /// Get the bottom element in the lattice, which abstracts no concrete value at all. For
+    /// booleans, this is Bot. For intervals, this is the empty interval.
+    fn bottom(&self) -> Self;
+    fn is_bottom(&self) -> bool;
 }
@@ -5,4 +5,23 @@ use crate::domains::value::AbstractValue;
 pub struct AbstractFunction {
     pub arguments: Vec<AbstractValue>,
     pub return_val: AbstractValue,
+    /// More precise summaries of the function for specific abstract inputs, e.g. the arguments of a
+    /// particular call site. The arguments of each context are covered by `arguments`.
+    pub contexts: Vec<CallContext>,
+}
+
+/// Summary of a function for one abstract input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallContext {
+    pub arguments: Vec<AbstractValue>,
+    pub return_val: AbstractValue,
+}
+
+impl AbstractFunction {
+    /// Returns the return value recorded for exactly the given abstract arguments, if any.
+    pub fn context(&self, arguments: &[AbstractValue]) -> Option<&AbstractValue> {
+        self.contexts.iter()
+            .find(|context| context.arguments == arguments)
+            .map(|context| &context.return_val)
+    }
 }
@@ -2,6 +2,51 @@ use crate::domains::domain::AbstractDomain;
 use std::cmp::Ordering;
 use crate::domains::booleans::AbstractBool;
 
+/// Concrete integer types that can be used as the bounds of an interval.
+pub trait Integer: Ord + Copy {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn checked_neg(self) -> Option<Self> {
+                    <$t>::checked_neg(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i128, u128);
+
 /// Represents an element in an interval, or +/- infinity for a bound of the interval.
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub enum IntervalElem<T: Ord + Copy> {
@@ -10,7 +55,9 @@ pub enum IntervalElem<T: Ord + Copy> {
     NegInf,
 }
 
-/// Represents an interval ranging between lower and upper.
+/// Represents an interval ranging between lower and upper. An interval whose lower bound is greater
+/// than its upper bound is empty, i.e. the bottom element. Operations only ever produce the
+/// canonical bottom `[Inf, NegInf]` so that all empty intervals compare equal.
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub struct Interval<T: Ord + Copy> {
     pub(crate) lower: IntervalElem<T>,
@@ -48,13 +95,40 @@ impl<T: Ord + Copy> AbstractDomain for Interval<T> {
 
     /// Merges two intervals such that the resulting interval contains both intervals
     fn join(&self, other: &Self) -> Self {
+        if self.is_bottom() {
+            return *other;
+        } else if other.is_bottom() {
+            return *self;
+        }
         Interval {
             lower: Ord::min(self.lower, other.lower),
             upper: Ord::max(self.upper, other.upper),
         }
     }
 
+    /// Intersects two intervals. Disjoint intervals have the empty intersection bottom.
+    fn meet(&self, other: &Self) -> Self {
+        let met = Interval {
+            lower: Ord::max(self.lower, other.lower),
+            upper: Ord::min(self.upper, other.upper),
+        };
+        if met.is_bottom() {
+            self.bottom()
+        } else {
+            met
+        }
+    }
+
+    fn leq(&self, other: &Self) -> bool {
+        self.is_bottom() || (other.lower <= self.lower && self.upper <= other.upper)
+    }
+
     fn widen(&self, other: &Self) -> Self {
+        if self.is_bottom() {
+            return *other;
+        } else if other.is_bottom() {
+            return *self;
+        }
         Interval {
             lower: if other.lower < self.lower {
                 IntervalElem::NegInf
@@ -69,12 +143,63 @@ impl<T: Ord + Copy> AbstractDomain for Interval<T> {
         }
     }
 
+    /// Only refines the bounds that were widened to infinity, which guarantees that a sequence of
+    /// narrowings stabilizes.
+    fn narrow(&self, other: &Self) -> Self {
+        if self.is_bottom() || other.is_bottom() {
+            return self.bottom();
+        }
+        Interval {
+            lower: if self.lower == IntervalElem::NegInf {
+                other.lower
+            } else {
+                self.lower
+            },
+            upper: if self.upper == IntervalElem::Inf {
+                other.upper
+            } else {
+                self.upper
+            },
+        }
+    }
+
     fn top(&self) -> Self {
         Interval {
             lower: IntervalElem::NegInf,
             upper: IntervalElem::Inf,
         }
     }
+
+    fn bottom(&self) -> Self {
+        Interval {
+            lower: IntervalElem::Inf,
+            upper: IntervalElem::NegInf,
+        }
+    }
+
+    fn is_bottom(&self) -> bool {
+        self.lower > self.upper
+    }
+}
+
+impl<T: Ord + Copy + std::fmt::Display> std::fmt::Display for IntervalElem<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntervalElem::Inf => write!(f, "+inf"),
+            IntervalElem::Elem(x) => write!(f, "{x}"),
+            IntervalElem::NegInf => write!(f, "-inf"),
+        }
+    }
+}
+
+impl<T: Ord + Copy + std::fmt::Display> std::fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_bottom() {
+            write!(f, "bottom")
+        } else {
+            write!(f, "[{}, {}]", self.lower, self.upper)
+        }
+    }
 }
 
 impl<T: Ord + Copy> From<T> for Interval<T> {
@@ -96,7 +221,9 @@ impl<T: Ord + Copy> Interval<T> {
 
     /// Abstract boolean equality operation
     pub(crate) fn equals(&self, other: &Self) -> AbstractBool {
-        if self.upper < other.lower || other.upper < self.lower {
+        if self.is_bottom() || other.is_bottom() {
+            AbstractBool::Bot
+        } else if self.upper < other.lower || other.upper < self.lower {
             // The two intervals don't overlap, always will be false
             AbstractBool::False
         } else if self.lower == self.upper && other.lower == other.upper && self.lower == other.lower && self.lower != IntervalElem::Inf && self.lower != IntervalElem::NegInf {
@@ -110,7 +237,9 @@ impl<T: Ord + Copy> Interval<T> {
 
     /// Abstract boolean less than operation
     pub(crate) fn less_than(&self, other: &Self) -> AbstractBool {
-        if self.upper < other.lower {
+        if self.is_bottom() || other.is_bottom() {
+            AbstractBool::Bot
+        } else if self.upper < other.lower {
             // The self interval is outside of and always less than the other interval
             AbstractBool::True
         } else if self.lower >= other.upper {
@@ -123,25 +252,293 @@ impl<T: Ord + Copy> Interval<T> {
     }
 }
 
-impl <T: Ord + Copy + std::ops::Add<Output = T>> std::ops::Add for IntervalElem<T>
-{
+impl<T: Integer> IntervalElem<T> {
+    /// The next smaller element. Infinite elements stay the same. Returns None if there is no
+    /// smaller element.
+    fn pred(self) -> Option<Self> {
+        match self {
+            IntervalElem::Elem(x) => x.checked_sub(T::ONE).map(IntervalElem::Elem),
+            _ => Some(self),
+        }
+    }
+
+    /// The next larger element. Infinite elements stay the same. Returns None if there is no
+    /// larger element.
+    fn succ(self) -> Option<Self> {
+        match self {
+            IntervalElem::Elem(x) => x.checked_add(T::ONE).map(IntervalElem::Elem),
+            _ => Some(self),
+        }
+    }
+}
+
+impl<T: Integer> Interval<T> {
+    /// Builds an interval from its bounds, returning None if the interval would be empty.
+    fn non_empty(lower: IntervalElem<T>, upper: IntervalElem<T>) -> Option<Self> {
+        if lower <= upper {
+            Some(Interval { lower, upper })
+        } else {
+            None
+        }
+    }
+
+    /// Returns None if either interval is empty, in which case no comparison can hold.
+    fn both_non_empty(&self, other: &Self) -> Option<()> {
+        if self.is_bottom() || other.is_bottom() {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    /// Refines the interval to the elements that are less than some element of `other`. Returns
+    /// None if no element of the interval is.
+    pub(crate) fn refine_less_than(&self, other: &Self) -> Option<Self> {
+        self.both_non_empty(other)?;
+        let upper = Ord::min(self.upper, other.upper.pred()?);
+        Self::non_empty(self.lower, upper)
+    }
+
+    /// Refines the interval to the elements that are less than or equal to some element of `other`.
+    /// Returns None if no element of the interval is.
+    pub(crate) fn refine_less_equal(&self, other: &Self) -> Option<Self> {
+        self.both_non_empty(other)?;
+        Self::non_empty(self.lower, Ord::min(self.upper, other.upper))
+    }
+
+    /// Refines the interval to the elements that are greater than some element of `other`. Returns
+    /// None if no element of the interval is.
+    pub(crate) fn refine_greater_than(&self, other: &Self) -> Option<Self> {
+        self.both_non_empty(other)?;
+        let lower = Ord::max(self.lower, other.lower.succ()?);
+        Self::non_empty(lower, self.upper)
+    }
+
+    /// Refines the interval to the elements that are greater than or equal to some element of
+    /// `other`. Returns None if no element of the interval is.
+    pub(crate) fn refine_greater_equal(&self, other: &Self) -> Option<Self> {
+        self.both_non_empty(other)?;
+        Self::non_empty(Ord::max(self.lower, other.lower), self.upper)
+    }
+
+    /// Refines the interval to the elements that are also in `other`. Returns None if the intervals
+    /// don't overlap.
+    pub(crate) fn refine_equal(&self, other: &Self) -> Option<Self> {
+        self.both_non_empty(other)?;
+        Self::non_empty(Ord::max(self.lower, other.lower), Ord::min(self.upper, other.upper))
+    }
+
+    /// Refines the interval to the elements that can differ from some element of `other`. Only
+    /// a singleton `other` at one of the bounds of the interval can be excluded. Returns None if
+    /// every element of the interval is always equal to `other`.
+    pub(crate) fn refine_not_equal(&self, other: &Self) -> Option<Self> {
+        self.both_non_empty(other)?;
+        if other.lower != other.upper || !matches!(other.lower, IntervalElem::Elem(_)) {
+            return Some(*self);
+        }
+        let excluded = other.lower;
+        if self.lower == excluded && self.upper == excluded {
+            None
+        } else if self.lower == excluded {
+            Self::non_empty(self.lower.succ()?, self.upper)
+        } else if self.upper == excluded {
+            Self::non_empty(self.lower, self.upper.pred()?)
+        } else {
+            Some(*self)
+        }
+    }
+}
+
+// Arithmetic on interval bounds. Results that don't fit in `T` are mapped to the infinity with the
+// same sign as the mathematical result, so the bounds computed from them stay sound.
+
+impl<T: Integer> IntervalElem<T> {
+    /// The sign of the element compared to zero.
+    fn signum(self) -> Ordering {
+        self.cmp(&IntervalElem::Elem(T::ZERO))
+    }
+
+    /// The infinity with the given sign, or zero if the sign is `Equal`.
+    fn inf_with_sign(sign: Ordering) -> Self {
+        match sign {
+            Ordering::Greater => IntervalElem::Inf,
+            Ordering::Less => IntervalElem::NegInf,
+            Ordering::Equal => IntervalElem::Elem(T::ZERO),
+        }
+    }
+
+    /// The absolute value of the element.
+    fn abs(self) -> Self {
+        match self {
+            IntervalElem::Elem(x) if x < T::ZERO => -self,
+            IntervalElem::Elem(_) => self,
+            _ => IntervalElem::Inf,
+        }
+    }
+}
+
+impl<T: Integer> std::ops::Add for IntervalElem<T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
         match (self, rhs) {
             (IntervalElem::NegInf, _) | (_, IntervalElem::NegInf) => IntervalElem::NegInf,
             (IntervalElem::Inf, _) | (_, IntervalElem::Inf) => IntervalElem::Inf,
-            (IntervalElem::Elem(l), IntervalElem::Elem(r)) => IntervalElem::Elem(l + r),
+            (IntervalElem::Elem(l), IntervalElem::Elem(r)) => match l.checked_add(r) {
+                Some(x) => IntervalElem::Elem(x),
+                // Overflow goes in the direction of the right operand
+                None => Self::inf_with_sign(rhs.signum()),
+            },
+        }
+    }
+}
+
+impl<T: Integer> std::ops::Sub for IntervalElem<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (IntervalElem::NegInf, _) | (_, IntervalElem::Inf) => IntervalElem::NegInf,
+            (IntervalElem::Inf, _) | (_, IntervalElem::NegInf) => IntervalElem::Inf,
+            (IntervalElem::Elem(l), IntervalElem::Elem(r)) => match l.checked_sub(r) {
+                Some(x) => IntervalElem::Elem(x),
+                None => Self::inf_with_sign(l.cmp(&r)),
+            },
         }
     }
 }
 
-impl <T: Ord + Copy + std::ops::Add> std::ops::Add for Interval<T>
-    where IntervalElem<T>: std::ops::Add<Output = IntervalElem<T>>
-{
+impl<T: Integer> std::ops::Mul for IntervalElem<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (IntervalElem::Elem(l), IntervalElem::Elem(r)) => match l.checked_mul(r) {
+                Some(x) => IntervalElem::Elem(x),
+                None => Self::inf_with_sign(if self.signum() == rhs.signum() { Ordering::Greater } else { Ordering::Less }),
+            },
+            // Zero times infinity is treated as zero since the infinite bound is never reached.
+            _ => match (self.signum(), rhs.signum()) {
+                (Ordering::Equal, _) | (_, Ordering::Equal) => IntervalElem::Elem(T::ZERO),
+                (a, b) if a == b => IntervalElem::Inf,
+                _ => IntervalElem::NegInf,
+            },
+        }
+    }
+}
+
+impl<T: Integer> std::ops::Div for IntervalElem<T> {
+    type Output = Self;
+
+    /// Truncating division. The divisor must not be zero.
+    fn div(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (IntervalElem::Elem(l), IntervalElem::Elem(r)) => match l.checked_div(r) {
+                Some(x) => IntervalElem::Elem(x),
+                // Only MIN / -1 overflows
+                None => IntervalElem::Inf,
+            },
+            // A finite value divided by an infinite one truncates to zero. The quotient of two
+            // infinite bounds is also taken to be zero, the remaining corners of the division
+            // always bound the result on each side of it.
+            (IntervalElem::Elem(_), _) | (IntervalElem::Inf | IntervalElem::NegInf, IntervalElem::Inf | IntervalElem::NegInf) => {
+                IntervalElem::Elem(T::ZERO)
+            }
+            _ => Self::inf_with_sign(if self.signum() == rhs.signum() { Ordering::Greater } else { Ordering::Less }),
+        }
+    }
+}
+
+impl<T: Integer> std::ops::Neg for IntervalElem<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            IntervalElem::Inf => IntervalElem::NegInf,
+            IntervalElem::NegInf => IntervalElem::Inf,
+            IntervalElem::Elem(x) => match x.checked_neg() {
+                Some(n) => IntervalElem::Elem(n),
+                None => Self::inf_with_sign(IntervalElem::Elem(x).signum().reverse()),
+            },
+        }
+    }
+}
+
+impl<T: Integer> Interval<T> {
+    /// Builds the smallest interval containing all of the given bounds.
+    fn hull(bounds: [IntervalElem<T>; 4]) -> Self {
+        Interval {
+            lower: *bounds.iter().min().unwrap(),
+            upper: *bounds.iter().max().unwrap(),
+        }
+    }
+
+    /// Restricts the interval to the values within `bounds`, e.g. the range of an integer type. If
+    /// the two don't overlap, the result is bottom.
+    pub(crate) fn clamp(&self, bounds: &Self) -> Self {
+        Self::non_empty(Ord::max(self.lower, bounds.lower), Ord::min(self.upper, bounds.upper))
+            .unwrap_or(self.bottom())
+    }
+
+    /// Maps the interval into `bounds` the way two's complement arithmetic wraps results that fall
+    /// outside of the range of an integer type. Intervals that are entirely one range-width above or
+    /// below `bounds` are shifted back into range, anything else that doesn't fit becomes `bounds`.
+    pub(crate) fn wrap(&self, bounds: &Self) -> Self {
+        if self.is_bottom() {
+            return *self;
+        } else if self.lower >= bounds.lower && self.upper <= bounds.upper {
+            return *self;
+        }
+
+        // The number of values in the range. For the full range of T this overflows to infinity,
+        // but then the out-of-range bounds are infinite as well and nothing can be recovered.
+        if let IntervalElem::Elem(modulus) = bounds.upper - bounds.lower + IntervalElem::Elem(T::ONE) {
+            let shifted = if self.lower > bounds.upper {
+                Some(*self - Interval::from(modulus))
+            } else if self.upper < bounds.lower {
+                Some(*self + Interval::from(modulus))
+            } else {
+                None
+            };
+            if let Some(shifted) = shifted {
+                if shifted.lower >= bounds.lower && shifted.upper <= bounds.upper {
+                    return shifted;
+                }
+            }
+        }
+        *bounds
+    }
+
+    /// Abstract check of whether a value in the interval falls outside of `bounds`, i.e. whether an
+    /// operation producing it overflows.
+    pub(crate) fn exceeds(&self, bounds: &Self) -> AbstractBool {
+        if self.is_bottom() {
+            AbstractBool::Bot
+        } else if self.lower >= bounds.lower && self.upper <= bounds.upper {
+            AbstractBool::False
+        } else if self.upper < bounds.lower || self.lower > bounds.upper {
+            AbstractBool::True
+        } else {
+            AbstractBool::Top
+        }
+    }
+
+    /// Splits the interval into its strictly negative and strictly positive parts, dropping zero.
+    fn split_at_zero(&self) -> (Option<Self>, Option<Self>) {
+        let zero = IntervalElem::Elem(T::ZERO);
+        let negative = zero.pred().and_then(|upper| Self::non_empty(self.lower, Ord::min(self.upper, upper)));
+        let positive = zero.succ().and_then(|lower| Self::non_empty(Ord::max(self.lower, lower), self.upper));
+        (negative, positive)
+    }
+}
+
+impl<T: Integer> std::ops::Add for Interval<T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
+        if self.is_bottom() || rhs.is_bottom() {
+            return self.bottom();
+        }
         Self {
             lower: self.lower + rhs.lower,
             upper: self.upper + rhs.upper,
@@ -149,6 +546,97 @@ impl <T: Ord + Copy + std::ops::Add> std::ops::Add for Interval<T>
     }
 }
 
+impl<T: Integer> std::ops::Sub for Interval<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        if self.is_bottom() || rhs.is_bottom() {
+            return self.bottom();
+        }
+        Self {
+            lower: self.lower - rhs.upper,
+            upper: self.upper - rhs.lower,
+        }
+    }
+}
+
+impl<T: Integer> std::ops::Mul for Interval<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self.is_bottom() || rhs.is_bottom() {
+            return self.bottom();
+        }
+        Self::hull([
+            self.lower * rhs.lower,
+            self.lower * rhs.upper,
+            self.upper * rhs.lower,
+            self.upper * rhs.upper,
+        ])
+    }
+}
+
+impl<T: Integer> std::ops::Div for Interval<T> {
+    type Output = Self;
+
+    /// Truncating division. Division by zero panics, so zero is excluded from the divisor and the
+    /// quotients for its negative and positive parts are joined. If the divisor can only be zero,
+    /// the division can never produce a value and the result is bottom.
+    fn div(self, rhs: Self) -> Self {
+        if self.is_bottom() || rhs.is_bottom() {
+            return self.bottom();
+        }
+        let divide = |divisor: Self| Self::hull([
+            self.lower / divisor.lower,
+            self.lower / divisor.upper,
+            self.upper / divisor.lower,
+            self.upper / divisor.upper,
+        ]);
+        match rhs.split_at_zero() {
+            (Some(negative), Some(positive)) => divide(negative).join(&divide(positive)),
+            (Some(divisor), None) | (None, Some(divisor)) => divide(divisor),
+            (None, None) => self.bottom(),
+        }
+    }
+}
+
+impl<T: Integer> std::ops::Rem for Interval<T> {
+    type Output = Self;
+
+    /// Truncating remainder. The remainder has the sign of the dividend and is smaller in
+    /// magnitude than both the dividend and the divisor. If the divisor can only be zero, the
+    /// operation can never produce a value and the result is bottom.
+    fn rem(self, rhs: Self) -> Self {
+        if self.is_bottom() || rhs.is_bottom() {
+            return self.bottom();
+        }
+        let zero = IntervalElem::Elem(T::ZERO);
+        let max_divisor = Ord::max(rhs.lower.abs(), rhs.upper.abs());
+        let bound = match max_divisor.pred() {
+            Some(bound) if max_divisor != zero => bound,
+            _ => return self.bottom(),
+        };
+
+        let lower = if self.lower >= zero { zero } else { Ord::max(self.lower, -bound) };
+        let upper = if self.upper <= zero { zero } else { Ord::min(self.upper, bound) };
+        Self { lower, upper }
+    }
+}
+
+impl<T: Integer> std::ops::Neg for Interval<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.is_bottom() {
+            return self;
+        }
+        Self {
+            lower: -self.upper,
+            upper: -self.lower,
+        }
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -220,4 +708,155 @@ mod tests {
             AbstractBool::Top
         );
     }
+
+    #[test]
+    fn test_refine_comparisons() {
+        let x = Interval::from_interval(0i128, 20);
+        assert_eq!(x.refine_less_than(&Interval::from(10)), Some(Interval::from_interval(0, 9)));
+        assert_eq!(x.refine_less_equal(&Interval::from(10)), Some(Interval::from_interval(0, 10)));
+        assert_eq!(x.refine_greater_than(&Interval::from(10)), Some(Interval::from_interval(11, 20)));
+        assert_eq!(x.refine_greater_equal(&Interval::from(10)), Some(Interval::from_interval(10, 20)));
+        assert_eq!(x.refine_equal(&Interval::from_interval(15, 30)), Some(Interval::from_interval(15, 20)));
+        assert_eq!(x.refine_not_equal(&Interval::from(0)), Some(Interval::from_interval(1, 20)));
+        assert_eq!(x.refine_not_equal(&Interval::from(5)), Some(x));
+
+        // Refining against an interval that can't satisfy the comparison is infeasible
+        assert_eq!(x.refine_less_than(&Interval::from(0)), None);
+        assert_eq!(x.refine_greater_than(&Interval::from(20)), None);
+        assert_eq!(x.refine_equal(&Interval::from_interval(30, 40)), None);
+        assert_eq!(Interval::from(3i128).refine_not_equal(&Interval::from(3)), None);
+
+        // Infinite bounds are kept when comparing against an unbounded interval
+        let top = Interval::from(0i128).top();
+        assert_eq!(top.refine_less_than(&Interval::from(10)), Some(Interval { lower: IntervalElem::NegInf, upper: IntervalElem::Elem(9) }));
+        assert_eq!(x.refine_less_than(&top), Some(x));
+
+        // There is no unsigned value less than 0
+        assert_eq!(Interval::from_interval(0u128, 5).refine_less_than(&Interval::from(0u128)), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Interval::from_interval(-2i128, 3);
+        let b = Interval::from_interval(4i128, 5);
+        assert_eq!(a + b, Interval::from_interval(2, 8));
+        assert_eq!(a - b, Interval::from_interval(-7, -1));
+        assert_eq!(a * b, Interval::from_interval(-10, 15));
+        assert_eq!(-a, Interval::from_interval(-3, 2));
+        assert_eq!(Interval::from_interval(-3i128, -2) * Interval::from_interval(-5, 4), Interval::from_interval(-12, 15));
+
+        // Division truncates toward zero
+        assert_eq!(Interval::from_interval(7i128, 20) / Interval::from_interval(2, 3), Interval::from_interval(2, 10));
+        assert_eq!(Interval::from_interval(-20i128, 7) / Interval::from_interval(2, 3), Interval::from_interval(-10, 3));
+        // Zero is excluded from the divisor
+        assert_eq!(Interval::from_interval(10i128, 20) / Interval::from_interval(-1, 2), Interval::from_interval(-20, 20));
+        assert_eq!(Interval::from_interval(10u128, 20) / Interval::from_interval(0, 2), Interval::from_interval(5, 20));
+
+        // The remainder has the sign of the dividend and is smaller than the divisor
+        assert_eq!(Interval::from_interval(0i128, 100) % Interval::from(7), Interval::from_interval(0, 6));
+        assert_eq!(Interval::from_interval(-100i128, 3) % Interval::from_interval(-7, 5), Interval::from_interval(-6, 3));
+    }
+
+    #[test]
+    fn test_arithmetic_infinite_bounds() {
+        let top = Interval::from(0i128).top();
+        let positive = Interval { lower: IntervalElem::Elem(1i128), upper: IntervalElem::Inf };
+        assert_eq!(top + Interval::from(1), top);
+        assert_eq!(positive - positive, top);
+        assert_eq!(positive * Interval::from(-2), Interval { lower: IntervalElem::NegInf, upper: IntervalElem::Elem(-2) });
+        assert_eq!(positive * Interval::from(0), Interval::from(0));
+        assert_eq!(-positive, Interval { lower: IntervalElem::NegInf, upper: IntervalElem::Elem(-1) });
+        assert_eq!(Interval::from(10i128) / positive, Interval::from_interval(0, 10));
+        assert_eq!(positive % Interval::from(4), Interval::from_interval(0, 3));
+
+        // Overflowing the underlying type goes to infinity instead of panicking
+        assert_eq!(Interval::from(i128::MAX) + Interval::from(1), Interval { lower: IntervalElem::Inf, upper: IntervalElem::Inf });
+        assert_eq!(Interval::from(0u128) - Interval::from(1), Interval { lower: IntervalElem::NegInf, upper: IntervalElem::NegInf });
+        assert_eq!(-Interval::from(i128::MIN), Interval { lower: IntervalElem::Inf, upper: IntervalElem::Inf });
+    }
+
+    #[test]
+    fn test_type_bounds() {
+        let u8_bounds = Interval::from_interval(0u128, 255);
+        assert_eq!(Interval::from_interval(10u128, 20).wrap(&u8_bounds), Interval::from_interval(10, 20));
+        assert_eq!(Interval::from_interval(256u128, 300).wrap(&u8_bounds), Interval::from_interval(0, 44));
+        assert_eq!(Interval::from_interval(200u128, 300).wrap(&u8_bounds), u8_bounds);
+        assert_eq!(Interval::from_interval(200u128, 300).clamp(&u8_bounds), Interval::from_interval(200, 255));
+
+        let i8_bounds = Interval::from_interval(-128i128, 127);
+        assert_eq!(Interval::from_interval(-130i128, -129).wrap(&i8_bounds), Interval::from_interval(126, 127));
+        assert_eq!(Interval::from_interval(0i128, 127).exceeds(&i8_bounds), AbstractBool::False);
+        assert_eq!(Interval::from_interval(100i128, 200).exceeds(&i8_bounds), AbstractBool::Top);
+        assert_eq!(Interval::from_interval(128i128, 200).exceeds(&i8_bounds), AbstractBool::True);
+
+        // Results that overflowed the full width of the underlying type are infinite
+        let i128_bounds = Interval::from_interval(i128::MIN, i128::MAX);
+        let overflowed = Interval::from(i128::MAX) + Interval::from(1);
+        assert_eq!(overflowed.wrap(&i128_bounds), i128_bounds);
+        assert_eq!(overflowed.exceeds(&i128_bounds), AbstractBool::True);
+    }
+
+    #[test]
+    fn test_meet_and_order() {
+        let x = Interval::from_interval(0i128, 20);
+        let y = Interval::from_interval(10i128, 30);
+        assert_eq!(x.meet(&y), Interval::from_interval(10, 20));
+        assert!(x.meet(&Interval::from_interval(25, 30)).is_bottom());
+        assert!(x.meet(&y).leq(&x));
+        assert!(x.meet(&y).leq(&y));
+        assert!(!x.leq(&y));
+        assert!(x.leq(&x.top()));
+        assert!(x.bottom().leq(&x));
+        assert!(!x.leq(&x.bottom()));
+    }
+
+    #[test]
+    fn test_narrow() {
+        let widened = Interval { lower: IntervalElem::Elem(0i128), upper: IntervalElem::Inf };
+        // Infinite bounds are refined, finite ones are kept
+        assert_eq!(widened.narrow(&Interval::from_interval(0, 10)), Interval::from_interval(0, 10));
+        assert_eq!(widened.narrow(&Interval::from_interval(5, 10)), Interval::from_interval(0, 10));
+        assert_eq!(Interval::from_interval(0i128, 20).narrow(&Interval::from_interval(5, 10)), Interval::from_interval(0, 20));
+        assert!(widened.narrow(&widened.bottom()).is_bottom());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Interval::from_interval(-3i128, 5).to_string(), "[-3, 5]");
+        assert_eq!(Interval::from(0i128).top().to_string(), "[-inf, +inf]");
+        assert_eq!(Interval::from(0u128).bottom().to_string(), "bottom");
+    }
+
+    #[test]
+    fn test_bottom() {
+        let x = Interval::from_interval(0i128, 20);
+        let bot = x.bottom();
+        assert!(bot.is_bottom());
+        assert!(!x.is_bottom());
+
+        // Bottom is the identity of join and widen
+        assert_eq!(bot.join(&x), x);
+        assert_eq!(x.join(&bot), x);
+        assert_eq!(bot.widen(&x), x);
+        assert_eq!(x.widen(&bot), x);
+
+        // Every operation on bottom produces bottom
+        assert_eq!(x + bot, bot);
+        assert_eq!(bot - x, bot);
+        assert_eq!(x * bot, bot);
+        assert_eq!(bot / x, bot);
+        assert_eq!(x % bot, bot);
+        assert_eq!(-bot, bot);
+        assert_eq!(x.equals(&bot), AbstractBool::Bot);
+        assert_eq!(bot.less_than(&x), AbstractBool::Bot);
+        assert_eq!(bot.exceeds(&x), AbstractBool::Bot);
+        assert_eq!(bot.wrap(&x), bot);
+        assert_eq!(x.refine_less_equal(&bot), None);
+        assert_eq!(bot.refine_not_equal(&x), None);
+
+        // Operations that can never produce a value produce bottom
+        assert_eq!(x / Interval::from(0), bot);
+        assert_eq!(x % Interval::from(0), bot);
+        assert_eq!(Interval::from_interval(300i128, 400).clamp(&Interval::from_interval(0, 255)), bot);
+    }
 }
@@ -0,0 +1,278 @@
+//! Generic checks of the laws every `AbstractDomain` has to satisfy, run on randomly generated
+//! elements. A domain is plugged in by giving a generator for its elements, e.g.
+//!
+//! ```ignore
+//! check_lattice_laws(|rng| random_bool(rng));
+//! ```
+//!
+//! Transfer functions (e.g. interval addition) are checked for monotonicity separately with
+//! `check_monotone_unary` and `check_monotone_binary`.
+
+extern crate stable_mir as smir;
+
+use crate::domains::booleans::AbstractBool;
+use crate::domains::domain::AbstractDomain;
+use crate::domains::interval::{Interval, IntervalElem};
+use crate::domains::{uint_bounds, AbstractValue};
+use crate::test_utils::XorShift;
+use smir::ty::UintTy;
+use std::fmt::Debug;
+
+/// Number of random cases per law.
+const CASES: usize = 500;
+
+/// Length of the ascending chains used to check that widening terminates.
+const CHAIN_LENGTH: usize = 50;
+
+/// Widening has to stabilize after at most this many strict increases. Intervals need 3 of them:
+/// from bottom to some interval, and then once for each bound that jumps to infinity.
+const MAX_WIDENING_STEPS: usize = 3;
+
+const SEED: u64 = 0x1a77;
+
+/// Checks the laws of the lattice operations, `top`, `bottom` and of widening and narrowing.
+pub(crate) fn check_lattice_laws<D, G>(generate: G)
+where
+    D: AbstractDomain + Clone + PartialEq + Debug,
+    G: Fn(&mut XorShift) -> D,
+{
+    let mut rng = XorShift::new(SEED);
+    for _ in 0..CASES {
+        let (a, b, c) = (generate(&mut rng), generate(&mut rng), generate(&mut rng));
+
+        // Partial order
+        assert!(a.leq(&a), "leq isn't reflexive for {a:?}");
+        if a.leq(&b) && b.leq(&a) {
+            assert_eq!(a, b, "leq isn't antisymmetric");
+        }
+        if a.leq(&b) && b.leq(&c) {
+            assert!(a.leq(&c), "leq isn't transitive for {a:?} <= {b:?} <= {c:?}");
+        }
+        assert!(a.bottom().leq(&a), "bottom isn't below {a:?}");
+        assert!(a.bottom().is_bottom(), "bottom of {a:?} isn't bottom");
+        assert_eq!(a.is_bottom(), a.leq(&a.bottom()), "is_bottom of {a:?} disagrees with leq");
+        assert!(a.leq(&a.top()), "{a:?} isn't below top");
+
+        // Join is the least upper bound
+        let join = a.join(&b);
+        assert_eq!(join, b.join(&a), "join isn't commutative for {a:?} and {b:?}");
+        assert_eq!(a.join(&b.join(&c)), join.join(&c), "join isn't associative for {a:?}, {b:?} and {c:?}");
+        assert_eq!(a.join(&a), a, "join isn't idempotent for {a:?}");
+        assert!(a.leq(&join) && b.leq(&join), "join of {a:?} and {b:?} isn't an upper bound");
+        if a.leq(&c) && b.leq(&c) {
+            assert!(join.leq(&c), "join of {a:?} and {b:?} isn't below the upper bound {c:?}");
+        }
+        assert_eq!(a.join(&a.bottom()), a, "bottom isn't the identity of join for {a:?}");
+
+        // Meet is the greatest lower bound
+        let meet = a.meet(&b);
+        assert_eq!(meet, b.meet(&a), "meet isn't commutative for {a:?} and {b:?}");
+        assert_eq!(a.meet(&a), a, "meet isn't idempotent for {a:?}");
+        assert!(meet.leq(&a) && meet.leq(&b), "meet of {a:?} and {b:?} isn't a lower bound");
+        if c.leq(&a) && c.leq(&b) {
+            assert!(c.leq(&meet), "meet of {a:?} and {b:?} isn't above the lower bound {c:?}");
+        }
+
+        // Widening over-approximates the join, and narrowing stays between its arguments
+        let widened = a.widen(&b);
+        assert!(a.leq(&widened) && b.leq(&widened), "widening {a:?} with {b:?} isn't an upper bound");
+        let below = a.meet(&b);
+        let narrowed = a.narrow(&below);
+        assert!(below.leq(&narrowed) && narrowed.leq(&a), "narrowing {a:?} with {below:?} isn't between the two");
+    }
+
+    // Widening along an ascending chain stabilizes after a few steps
+    for _ in 0..CASES / 10 {
+        let mut element = generate(&mut rng);
+        let mut widened = element.clone();
+        let mut steps = 0;
+        for _ in 0..CHAIN_LENGTH {
+            element = element.join(&generate(&mut rng));
+            let next = widened.widen(&widened.join(&element));
+            if next != widened {
+                steps += 1;
+            }
+            widened = next;
+        }
+        assert!(steps <= MAX_WIDENING_STEPS, "widening took {steps} steps to stabilize at {widened:?}");
+        assert!(element.leq(&widened), "widening ended at {widened:?}, below the chain element {element:?}");
+    }
+}
+
+/// Checks that `op` is monotone: applying it to larger arguments gives a larger result.
+pub(crate) fn check_monotone_unary<D, R, G, F>(generate: G, op: F)
+where
+    D: AbstractDomain + Clone + Debug,
+    R: AbstractDomain + Debug,
+    G: Fn(&mut XorShift) -> D,
+    F: Fn(&D) -> R,
+{
+    let mut rng = XorShift::new(SEED);
+    for _ in 0..CASES {
+        let a = generate(&mut rng);
+        let larger = a.join(&generate(&mut rng));
+        let (result, larger_result) = (op(&a), op(&larger));
+        assert!(result.leq(&larger_result), "not monotone: {a:?} gives {result:?}, but {larger:?} gives {larger_result:?}");
+    }
+}
+
+/// Checks that `op` is monotone in both of its arguments.
+pub(crate) fn check_monotone_binary<D, R, G, F>(generate: G, op: F)
+where
+    D: AbstractDomain + Clone + Debug,
+    R: AbstractDomain + Debug,
+    G: Fn(&mut XorShift) -> D,
+    F: Fn(&D, &D) -> R,
+{
+    let mut rng = XorShift::new(SEED);
+    for _ in 0..CASES {
+        let (a, b) = (generate(&mut rng), generate(&mut rng));
+        let (larger_a, larger_b) = (a.join(&generate(&mut rng)), b.join(&generate(&mut rng)));
+        let (result, larger_result) = (op(&a, &b), op(&larger_a, &larger_b));
+        assert!(
+            result.leq(&larger_result),
+            "not monotone: {a:?} and {b:?} give {result:?}, but {larger_a:?} and {larger_b:?} give {larger_result:?}",
+        );
+    }
+}
+
+/// Checks that `op` is bottom-strict: its result is bottom as soon as one of its arguments is.
+pub(crate) fn check_strict_unary<D, R, G, F>(generate: G, op: F)
+where
+    D: AbstractDomain + Debug,
+    R: AbstractDomain + Debug,
+    G: Fn(&mut XorShift) -> D,
+    F: Fn(&D) -> R,
+{
+    let mut rng = XorShift::new(SEED);
+    for _ in 0..CASES {
+        let bottom = generate(&mut rng).bottom();
+        let result = op(&bottom);
+        assert!(result.is_bottom(), "not strict: {bottom:?} gives {result:?}");
+    }
+}
+
+/// Checks that `op` is bottom-strict in both of its arguments.
+pub(crate) fn check_strict_binary<D, R, G, F>(generate: G, op: F)
+where
+    D: AbstractDomain + Debug,
+    R: AbstractDomain + Debug,
+    G: Fn(&mut XorShift) -> D,
+    F: Fn(&D, &D) -> R,
+{
+    let mut rng = XorShift::new(SEED);
+    for _ in 0..CASES {
+        let a = generate(&mut rng);
+        let bottom = a.bottom();
+        let (left, right) = (op(&bottom, &a), op(&a, &bottom));
+        assert!(left.is_bottom(), "not strict: {bottom:?} and {a:?} give {left:?}");
+        assert!(right.is_bottom(), "not strict: {a:?} and {bottom:?} give {right:?}");
+    }
+}
+
+pub(crate) fn random_bool(rng: &mut XorShift) -> AbstractBool {
+    match rng.next_u64() % 4 {
+        0 => AbstractBool::Bot,
+        1 => AbstractBool::True,
+        2 => AbstractBool::False,
+        _ => AbstractBool::Top,
+    }
+}
+
+/// Random interval with small bounds, so that random intervals often overlap. Some intervals are
+/// bottom or have infinite bounds.
+pub(crate) fn random_interval(rng: &mut XorShift) -> Interval<i128> {
+    let bound = |rng: &mut XorShift| match rng.next_u64() % 10 {
+        0 => None,
+        _ => Some(rng.range_i128(-20, 20)),
+    };
+    if rng.next_u64() % 10 == 0 {
+        return Interval::from(0).bottom();
+    }
+    let (lower, upper) = match (bound(rng), bound(rng)) {
+        (Some(x), Some(y)) => (Some(x.min(y)), Some(x.max(y))),
+        bounds => bounds,
+    };
+    Interval {
+        lower: lower.map_or(IntervalElem::NegInf, IntervalElem::Elem),
+        upper: upper.map_or(IntervalElem::Inf, IntervalElem::Elem),
+    }
+}
+
+/// Random interval of non-negative numbers, for checking unsigned intervals.
+pub(crate) fn random_unsigned_interval(rng: &mut XorShift) -> Interval<u128> {
+    if rng.next_u64() % 10 == 0 {
+        return Interval::from(0).bottom();
+    }
+    let (x, y) = (rng.range_u128(0, 40), rng.range_u128(0, 40));
+    let upper = if rng.next_u64() % 10 == 0 { IntervalElem::Inf } else { IntervalElem::Elem(x.max(y)) };
+    Interval { lower: IntervalElem::Elem(x.min(y)), upper }
+}
+
+/// Random `u8` value, or Uninit. Uninit takes the place of the bottom interval, which is above it,
+/// so that widening chains still stabilize within `MAX_WIDENING_STEPS`.
+pub(crate) fn random_value(rng: &mut XorShift) -> AbstractValue {
+    let interval = random_unsigned_interval(rng).meet(&uint_bounds(UintTy::U8));
+    if interval.is_bottom() || rng.next_u64() % 10 == 0 {
+        AbstractValue::Uninit
+    } else {
+        AbstractValue::UintInterval(UintTy::U8, interval)
+    }
+}
+
+#[test]
+fn booleans_are_a_lattice() {
+    check_lattice_laws(random_bool);
+}
+
+#[test]
+fn boolean_operations_are_monotone() {
+    check_monotone_unary(random_bool, AbstractBool::not);
+    check_monotone_binary(random_bool, AbstractBool::equals);
+    check_monotone_binary(random_bool, AbstractBool::and);
+    check_monotone_binary(random_bool, AbstractBool::or);
+    check_monotone_binary(random_bool, AbstractBool::xor);
+
+    check_strict_unary(random_bool, AbstractBool::not);
+    check_strict_binary(random_bool, AbstractBool::equals);
+    check_strict_binary(random_bool, AbstractBool::and);
+    check_strict_binary(random_bool, AbstractBool::or);
+    check_strict_binary(random_bool, AbstractBool::xor);
+}
+
+#[test]
+fn intervals_are_a_lattice() {
+    check_lattice_laws(random_interval);
+    check_lattice_laws(random_unsigned_interval);
+}
+
+#[test]
+fn values_are_a_lattice() {
+    check_lattice_laws(random_value);
+}
+
+#[test]
+fn interval_operations_are_monotone() {
+    check_monotone_binary(random_interval, |a, b| *a + *b);
+    check_monotone_binary(random_interval, |a, b| *a - *b);
+    check_monotone_binary(random_interval, |a, b| *a * *b);
+    check_monotone_binary(random_interval, |a, b| *a / *b);
+    check_monotone_binary(random_interval, |a, b| *a % *b);
+    check_monotone_unary(random_interval, |a| -*a);
+    check_monotone_binary(random_interval, Interval::equals);
+    check_monotone_binary(random_interval, Interval::less_than);
+
+    check_monotone_binary(random_unsigned_interval, |a, b| *a + *b);
+    check_monotone_binary(random_unsigned_interval, |a, b| *a - *b);
+    check_monotone_binary(random_unsigned_interval, |a, b| *a / *b);
+    check_monotone_binary(random_unsigned_interval, Interval::less_than);
+
+    check_strict_binary(random_interval, |a, b| *a + *b);
+    check_strict_binary(random_interval, |a, b| *a - *b);
+    check_strict_binary(random_interval, |a, b| *a * *b);
+    check_strict_binary(random_interval, |a, b| *a / *b);
+    check_strict_binary(random_interval, |a, b| *a % *b);
+    check_strict_unary(random_interval, |a| -*a);
+    check_strict_binary(random_interval, Interval::equals);
+    check_strict_binary(random_interval, Interval::less_than);
+}
@@ -7,7 +7,8 @@ use crate::domains::booleans;
 use crate::domains::domain::AbstractDomain;
 use crate::domains::interval;
 
-use smir::ty::{TyKind, RigidTy};
+use smir::mir::BinOp;
+use smir::ty::{IntTy, TyKind, RigidTy, UintTy};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AbstractValue {
@@ -19,9 +20,18 @@ pub enum AbstractValue {
     //
     // Note: float values in rust (e.g. f64) do not implement Ord, only PartialOrd, so they can't
     //  be intervals.
-    IntInterval(interval::Interval<i128>),
-    UintInterval(interval::Interval<u128>),
+    //
+    // The concrete integer type is kept alongside the interval so that the bounds of the type
+    // (e.g. [0, 255] for u8) are known when creating top values and when checking for overflow.
+    IntInterval(IntTy, interval::Interval<i128>),
+    UintInterval(UintTy, interval::Interval<u128>),
     Tuple(Vec<AbstractValue>),
+    // Arrays and slices are summarized by a single abstract value that covers all of their
+    // elements, along with the interval of possible lengths.
+    Array(Box<AbstractValue>, interval::Interval<u128>),
+    // Reference to a value. The referenced value itself isn't tracked, instead the reference holds
+    // an abstraction of the value it points to.
+    Ref(Box<AbstractValue>),
     // Value that represents an unitialized value.Can be explicitly created through a statement like Deinit.
     Uninit,
 }
@@ -31,18 +41,97 @@ impl AbstractDomain for AbstractValue {
         match (self, other) {
             (AbstractValue::Bool(a), AbstractValue::Bool(b)) => AbstractValue::Bool(a.join(b)),
             // TODO(klinvill): handle conversions between int, uint, etc.
-            (AbstractValue::IntInterval(a), AbstractValue::IntInterval(b)) => AbstractValue::IntInterval(a.join(b)),
-            (AbstractValue::UintInterval(a), AbstractValue::UintInterval(b)) => AbstractValue::UintInterval(a.join(b)),
+            (AbstractValue::IntInterval(ty, a), AbstractValue::IntInterval(_, b)) => AbstractValue::IntInterval(*ty, a.join(b)),
+            (AbstractValue::UintInterval(ty, a), AbstractValue::UintInterval(_, b)) => AbstractValue::UintInterval(*ty, a.join(b)),
+            (AbstractValue::Tuple(a), AbstractValue::Tuple(b)) if a.len() == b.len() => {
+                AbstractValue::Tuple(a.iter().zip(b).map(|(x, y)| x.join(y)).collect())
+            }
+            (AbstractValue::Array(a, a_len), AbstractValue::Array(b, b_len)) => AbstractValue::Array(Box::new(a.join(b)), a_len.join(b_len)),
+            (AbstractValue::Ref(a), AbstractValue::Ref(b)) => AbstractValue::Ref(Box::new(a.join(b))),
+            // Reading an uninitialized value is undefined behavior, so joining with Uninit can
+            // simply keep the other value.
+            (AbstractValue::Uninit, x) | (x, AbstractValue::Uninit) => x.clone(),
             (_, _) => panic!("Can only perform operations on abstract values of the same type (e.g. Bool or IntInterval)"),
         }
     }
 
+    fn meet(&self, other: &Self) -> Self {
+        match (self, other) {
+            (AbstractValue::Bool(a), AbstractValue::Bool(b)) => AbstractValue::Bool(a.meet(b)),
+            (AbstractValue::IntInterval(ty, a), AbstractValue::IntInterval(_, b)) => AbstractValue::IntInterval(*ty, a.meet(b)),
+            (AbstractValue::UintInterval(ty, a), AbstractValue::UintInterval(_, b)) => AbstractValue::UintInterval(*ty, a.meet(b)),
+            (AbstractValue::Tuple(a), AbstractValue::Tuple(b)) if a.len() == b.len() => {
+                AbstractValue::Tuple(a.iter().zip(b).map(|(x, y)| x.meet(y)).collect())
+            }
+            (AbstractValue::Array(a, a_len), AbstractValue::Array(b, b_len)) => AbstractValue::Array(Box::new(a.meet(b)), a_len.meet(b_len)),
+            (AbstractValue::Ref(a), AbstractValue::Ref(b)) => AbstractValue::Ref(Box::new(a.meet(b))),
+            // Uninit acts as the least value in joins, so it is also the meet of anything.
+            (AbstractValue::Uninit, _) | (_, AbstractValue::Uninit) => AbstractValue::Uninit,
+            (_, _) => panic!("Can only perform operations on abstract values of the same type (e.g. Bool or IntInterval)"),
+        }
+    }
+
+    /// Values of different kinds (e.g. a Bool and an IntInterval) are incomparable.
+    fn leq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AbstractValue::Bool(a), AbstractValue::Bool(b)) => a.leq(b),
+            (AbstractValue::IntInterval(_, a), AbstractValue::IntInterval(_, b)) => a.leq(b),
+            (AbstractValue::UintInterval(_, a), AbstractValue::UintInterval(_, b)) => a.leq(b),
+            (AbstractValue::Tuple(a), AbstractValue::Tuple(b)) if a.len() == b.len() => {
+                a.iter().zip(b).all(|(x, y)| x.leq(y))
+            }
+            (AbstractValue::Array(a, a_len), AbstractValue::Array(b, b_len)) => a.leq(b) && a_len.leq(b_len),
+            (AbstractValue::Ref(a), AbstractValue::Ref(b)) => a.leq(b),
+            (AbstractValue::Uninit, _) => true,
+            // Uninit is strictly below the bottom values of the other kinds, so that leq stays
+            // antisymmetric.
+            (_, AbstractValue::Uninit) => false,
+            (x, _) => x.is_bottom(),
+        }
+    }
+
     fn widen(&self, other: &Self) -> Self {
         match (self, other) {
             (AbstractValue::Bool(a), AbstractValue::Bool(b)) => AbstractValue::Bool(a.join(b)),
             // TODO(klinvill): handle conversions between int, uint, etc.
-            (AbstractValue::IntInterval(a), AbstractValue::IntInterval(b)) => AbstractValue::IntInterval(a.widen(b)),
-            (AbstractValue::UintInterval(a), AbstractValue::UintInterval(b)) => AbstractValue::UintInterval(a.widen(b)),
+            // Widening to infinity can stop at the bounds of the type
+            (AbstractValue::IntInterval(ty, a), AbstractValue::IntInterval(_, b)) => {
+                AbstractValue::IntInterval(*ty, a.widen(b).clamp(&int_bounds(*ty)))
+            }
+            (AbstractValue::UintInterval(ty, a), AbstractValue::UintInterval(_, b)) => {
+                AbstractValue::UintInterval(*ty, a.widen(b).clamp(&uint_bounds(*ty)))
+            }
+            (AbstractValue::Tuple(a), AbstractValue::Tuple(b)) if a.len() == b.len() => {
+                AbstractValue::Tuple(a.iter().zip(b).map(|(x, y)| x.widen(y)).collect())
+            }
+            (AbstractValue::Array(a, a_len), AbstractValue::Array(b, b_len)) => {
+                AbstractValue::Array(Box::new(a.widen(b)), a_len.widen(b_len).clamp(&length_bounds()))
+            }
+            (AbstractValue::Ref(a), AbstractValue::Ref(b)) => AbstractValue::Ref(Box::new(a.widen(b))),
+            (AbstractValue::Uninit, x) | (x, AbstractValue::Uninit) => x.clone(),
+            (_, _) => panic!("Can only perform operations on abstract values of the same type (e.g. Bool or IntInterval)"),
+        }
+    }
+
+    fn narrow(&self, other: &Self) -> Self {
+        match (self, other) {
+            (AbstractValue::Bool(a), AbstractValue::Bool(b)) => AbstractValue::Bool(a.narrow(b)),
+            // Widening stops at the bounds of the type rather than at infinity, so those bounds
+            // are the ones that narrowing may refine.
+            (AbstractValue::IntInterval(ty, a), AbstractValue::IntInterval(_, b)) => {
+                AbstractValue::IntInterval(*ty, narrow_typed(a, b, &int_bounds(*ty)))
+            }
+            (AbstractValue::UintInterval(ty, a), AbstractValue::UintInterval(_, b)) => {
+                AbstractValue::UintInterval(*ty, narrow_typed(a, b, &uint_bounds(*ty)))
+            }
+            (AbstractValue::Tuple(a), AbstractValue::Tuple(b)) if a.len() == b.len() => {
+                AbstractValue::Tuple(a.iter().zip(b).map(|(x, y)| x.narrow(y)).collect())
+            }
+            (AbstractValue::Array(a, a_len), AbstractValue::Array(b, b_len)) => {
+                AbstractValue::Array(Box::new(a.narrow(b)), narrow_typed(a_len, b_len, &length_bounds()))
+            }
+            (AbstractValue::Ref(a), AbstractValue::Ref(b)) => AbstractValue::Ref(Box::new(a.narrow(b))),
+            (AbstractValue::Uninit, _) | (_, AbstractValue::Uninit) => AbstractValue::Uninit,
             (_, _) => panic!("Can only perform operations on abstract values of the same type (e.g. Bool or IntInterval)"),
         }
     }
@@ -50,14 +139,206 @@ impl AbstractDomain for AbstractValue {
     fn top(&self) -> Self {
         match self {
             AbstractValue::Bool(x) => AbstractValue::Bool(x.top()),
-            AbstractValue::IntInterval(x) => AbstractValue::IntInterval(x.top()),
-            AbstractValue::UintInterval(x) => AbstractValue::UintInterval(x.top()),
+            AbstractValue::IntInterval(ty, _) => AbstractValue::IntInterval(*ty, int_bounds(*ty)),
+            AbstractValue::UintInterval(ty, _) => AbstractValue::UintInterval(*ty, uint_bounds(*ty)),
             AbstractValue::Tuple(avs) => {
                 AbstractValue::Tuple(avs.iter().map(|x| x.top()).collect())
             }
+            AbstractValue::Array(x, _) => AbstractValue::Array(Box::new(x.top()), length_bounds()),
+            AbstractValue::Ref(x) => AbstractValue::Ref(Box::new(x.top())),
+            AbstractValue::Uninit => AbstractValue::Uninit,
+        }
+    }
+
+    fn bottom(&self) -> Self {
+        match self {
+            AbstractValue::Bool(x) => AbstractValue::Bool(x.bottom()),
+            AbstractValue::IntInterval(ty, x) => AbstractValue::IntInterval(*ty, x.bottom()),
+            AbstractValue::UintInterval(ty, x) => AbstractValue::UintInterval(*ty, x.bottom()),
+            AbstractValue::Tuple(avs) => {
+                AbstractValue::Tuple(avs.iter().map(|x| x.bottom()).collect())
+            }
+            AbstractValue::Array(x, len) => AbstractValue::Array(Box::new(x.bottom()), len.bottom()),
+            AbstractValue::Ref(x) => AbstractValue::Ref(Box::new(x.bottom())),
             AbstractValue::Uninit => AbstractValue::Uninit,
         }
     }
+
+    /// A tuple is bottom as soon as one of its entries is, since no concrete tuple can be built
+    /// without a value for each entry. The same goes for references to bottom. An array summarizes
+    /// all of its elements, so it may still be empty when its element is bottom. It is only bottom
+    /// if it can't have any length. Uninit is bottom as well, since no execution reads it.
+    fn is_bottom(&self) -> bool {
+        match self {
+            AbstractValue::Bool(x) => x.is_bottom(),
+            AbstractValue::IntInterval(_, x) => x.is_bottom(),
+            AbstractValue::UintInterval(_, x) => x.is_bottom(),
+            AbstractValue::Tuple(avs) => avs.iter().any(|x| x.is_bottom()),
+            AbstractValue::Ref(x) => x.is_bottom(),
+            AbstractValue::Array(_, len) => len.is_bottom(),
+            AbstractValue::Uninit => true,
+        }
+    }
+}
+
+impl std::fmt::Display for AbstractValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbstractValue::Bool(b) => write!(f, "{b}"),
+            AbstractValue::IntInterval(_, i) => write!(f, "{i}"),
+            AbstractValue::UintInterval(_, i) => write!(f, "{i}"),
+            AbstractValue::Tuple(avs) => {
+                let entries: Vec<String> = avs.iter().map(|av| av.to_string()).collect();
+                write!(f, "({})", entries.join(", "))
+            }
+            AbstractValue::Array(elem, len) => write!(f, "[{elem}; {len}]"),
+            AbstractValue::Ref(pointee) => write!(f, "&{pointee}"),
+            AbstractValue::Uninit => write!(f, "uninit"),
+        }
+    }
+}
+
+/// How arithmetic operations handle results that don't fit in the type of their operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowMode {
+    /// Results wrap around, as for MIR `BinaryOp`s that aren't checked for overflow.
+    Wrap,
+    /// Overflowing panics, so only results that fit in the type are ever produced. This is the
+    /// behavior of the `CheckedBinaryOp`s MIR emits when overflow checks are enabled.
+    Panic,
+}
+
+/// Width in bits of `isize` and `usize` on the compilation target.
+pub(crate) fn pointer_width() -> u32 {
+    smir::target::MachineInfo::target_pointer_width().bits() as u32
+}
+
+/// Width in bits of a signed integer type.
+fn int_width(ty: IntTy) -> u32 {
+    match ty {
+        IntTy::Isize => pointer_width(),
+        IntTy::I8 => 8,
+        IntTy::I16 => 16,
+        IntTy::I32 => 32,
+        IntTy::I64 => 64,
+        IntTy::I128 => 128,
+    }
+}
+
+/// Width in bits of an unsigned integer type.
+fn uint_width(ty: UintTy) -> u32 {
+    match ty {
+        UintTy::Usize => pointer_width(),
+        UintTy::U8 => 8,
+        UintTy::U16 => 16,
+        UintTy::U32 => 32,
+        UintTy::U64 => 64,
+        UintTy::U128 => 128,
+    }
+}
+
+/// The interval [MIN, MAX] of a signed integer type.
+pub(crate) fn int_bounds(ty: IntTy) -> interval::Interval<i128> {
+    let shift = 128 - int_width(ty);
+    interval::Interval::from_interval(i128::MIN >> shift, i128::MAX >> shift)
+}
+
+/// The interval [MIN, MAX] of an unsigned integer type.
+pub(crate) fn uint_bounds(ty: UintTy) -> interval::Interval<u128> {
+    interval::Interval::from_interval(0, u128::MAX >> (128 - uint_width(ty)))
+}
+
+/// The possible lengths of a slice, i.e. the values of `usize`.
+pub(crate) fn length_bounds() -> interval::Interval<u128> {
+    uint_bounds(UintTy::Usize)
+}
+
+/// The length of an array type (e.g. `[u8; 4]`) or of a repeat expression (e.g. `[0; 4]`). Lengths
+/// that aren't known constants (e.g. generic parameters) could be any `usize`.
+pub(crate) fn array_length(len: &smir::ty::Const) -> interval::Interval<u128> {
+    const_bits(len).map(interval::Interval::from).unwrap_or_else(|_| length_bounds())
+}
+
+/// Interprets the raw bits of a signed integer (e.g. a `SwitchInt` value) as a value of the type.
+pub(crate) fn int_from_bits(ty: IntTy, bits: u128) -> i128 {
+    // Sign-extend from the width of the type
+    let shift = 128 - int_width(ty);
+    ((bits << shift) as i128) >> shift
+}
+
+/// Reads the raw bits of an integer constant from its allocation, honoring the endianness of the
+/// compilation target. The bits are zero-extended to 128 bits.
+fn const_bits(constant: &smir::ty::Const) -> Result<u128, Error> {
+    let alloc = match constant.kind() {
+        smir::ty::ConstantKind::Allocated(alloc) => alloc,
+        kind => return Err(Error::with_message(
+            ErrorKind::NotImplementedError,
+            format!("Constants of kind {:?} are not supported", kind),
+        )),
+    };
+
+    // Integer constants must be fully initialized and fit in 128 bits
+    let bytes: Option<Vec<u8>> = alloc.bytes.iter().cloned().collect();
+    let bytes = match bytes {
+        Some(bytes) if !bytes.is_empty() && bytes.len() <= 16 => bytes,
+        _ => return Err(Error::with_message(
+            ErrorKind::InterpreterError,
+            format!("Unexpected bytes for an integer constant: {:?}", alloc.bytes),
+        )),
+    };
+
+    Ok(bits_from_bytes(&bytes, smir::target::MachineInfo::target_endianess()))
+}
+
+/// Assembles integer bytes in the given byte order into a zero-extended 128 bit value.
+fn bits_from_bytes(bytes: &[u8], endian: smir::target::Endian) -> u128 {
+    let fold = |bits: u128, byte: &u8| (bits << 8) | u128::from(*byte);
+    match endian {
+        // The most significant byte comes last
+        smir::target::Endian::Little => bytes.iter().rev().fold(0, fold),
+        smir::target::Endian::Big => bytes.iter().fold(0, fold),
+    }
+}
+
+/// Applies an arithmetic binary operator to two intervals without regard for the bounds of their
+/// type.
+fn interval_arithmetic<T: interval::Integer>(op: &BinOp, left: interval::Interval<T>, right: interval::Interval<T>) -> Result<interval::Interval<T>, Error> {
+    match op {
+        BinOp::Add => Ok(left + right),
+        BinOp::Sub => Ok(left - right),
+        BinOp::Mul => Ok(left * right),
+        BinOp::Div => Ok(left / right),
+        BinOp::Rem => Ok(left % right),
+        _ => Err(Error::with_message(
+            ErrorKind::NotImplementedError,
+            format!("Not an arithmetic operator: {:?}", op),
+        )),
+    }
+}
+
+/// Narrows an interval of an integer type, treating bounds at the limits of the type as if they
+/// had been widened to infinity.
+fn narrow_typed<T: interval::Integer>(widened: &interval::Interval<T>, other: &interval::Interval<T>, bounds: &interval::Interval<T>) -> interval::Interval<T> {
+    let mut unbounded = *widened;
+    if !unbounded.is_bottom() {
+        if unbounded.lower <= bounds.lower {
+            unbounded.lower = interval::IntervalElem::NegInf;
+        }
+        if unbounded.upper >= bounds.upper {
+            unbounded.upper = interval::IntervalElem::Inf;
+        }
+    }
+    unbounded.narrow(other).clamp(bounds)
+}
+
+/// Fits the result of an operation into the bounds of its type according to the overflow mode.
+/// Returns the fitted result along with whether the operation may overflow.
+fn fit_to_bounds<T: interval::Integer>(result: interval::Interval<T>, bounds: &interval::Interval<T>, mode: OverflowMode) -> (interval::Interval<T>, booleans::AbstractBool) {
+    let overflow = result.exceeds(bounds);
+    match mode {
+        OverflowMode::Wrap => (result.wrap(bounds), overflow),
+        OverflowMode::Panic => (result.clamp(bounds), overflow),
+    }
 }
 
 impl AbstractValue {
@@ -66,17 +347,76 @@ impl AbstractValue {
     pub fn new(ty: &smir::ty::Ty) -> Result<Self, Error> {
         match ty.kind() {
             TyKind::RigidTy(RigidTy::Bool) => Ok(AbstractValue::Bool(booleans::AbstractBool::Top)),
-            TyKind::RigidTy(RigidTy::Int(_)) => Ok(AbstractValue::IntInterval(
-                interval::Interval::from(0).top(),
+            TyKind::RigidTy(RigidTy::Int(int_ty)) => Ok(AbstractValue::IntInterval(
+                int_ty,
+                int_bounds(int_ty),
             )),
-            TyKind::RigidTy(RigidTy::Uint(_)) => Ok(AbstractValue::UintInterval(
-                interval::Interval::from(0).top(),
+            TyKind::RigidTy(RigidTy::Uint(uint_ty)) => Ok(AbstractValue::UintInterval(
+                uint_ty,
+                uint_bounds(uint_ty),
             )),
             TyKind::RigidTy(RigidTy::Tuple(tys)) => {
                 let try_avs: Result<Vec<AbstractValue>, _> =
                     tys.iter().map(|t| AbstractValue::new(&t)).collect();
                 try_avs.map(AbstractValue::Tuple)
             }
+            TyKind::RigidTy(RigidTy::Array(elem_ty, len)) => {
+                AbstractValue::new(&elem_ty).map(|elem| AbstractValue::Array(Box::new(elem), array_length(&len)))
+            }
+            TyKind::RigidTy(RigidTy::Slice(elem_ty)) => {
+                AbstractValue::new(&elem_ty).map(|elem| AbstractValue::Array(Box::new(elem), length_bounds()))
+            }
+            TyKind::RigidTy(RigidTy::Ref(_, pointee_ty, _)) => {
+                AbstractValue::new(&pointee_ty).map(|pointee| AbstractValue::Ref(Box::new(pointee)))
+            }
+            _ => Err(Error::new(ErrorKind::NotImplementedError)),
+        }
+    }
+
+    /// Creates the abstract value of a constant. Booleans and integers are supported.
+    pub fn from_const(constant: &smir::ty::Const) -> Result<Self, Error> {
+        match constant.ty().kind() {
+            TyKind::RigidTy(RigidTy::Bool) => Ok(AbstractValue::Bool(booleans::AbstractBool::from(constant))),
+            TyKind::RigidTy(RigidTy::Int(int_ty)) => {
+                let bits = const_bits(constant)?;
+                Ok(AbstractValue::IntInterval(int_ty, interval::Interval::from(int_from_bits(int_ty, bits))))
+            }
+            TyKind::RigidTy(RigidTy::Uint(uint_ty)) => {
+                let bits = const_bits(constant)?;
+                Ok(AbstractValue::UintInterval(uint_ty, interval::Interval::from(bits)))
+            }
+            _ => Err(Error::with_message(
+                ErrorKind::NotImplementedError,
+                format!("Constants of type {:?} are not supported", constant.ty()),
+            )),
+        }
+    }
+
+    /// Applies an arithmetic binary operator (e.g. `Add` or `Div`) to two integer values. Returns
+    /// the result according to the overflow mode along with an abstract flag that indicates whether
+    /// the operation overflows the type of its operands.
+    pub fn arithmetic(&self, op: &BinOp, other: &Self, mode: OverflowMode) -> Result<(Self, booleans::AbstractBool), Error> {
+        match (self, other) {
+            (AbstractValue::IntInterval(ty, l), AbstractValue::IntInterval(_, r)) => {
+                let (result, overflow) = fit_to_bounds(interval_arithmetic(op, *l, *r)?, &int_bounds(*ty), mode);
+                Ok((AbstractValue::IntInterval(*ty, result), overflow))
+            }
+            (AbstractValue::UintInterval(ty, l), AbstractValue::UintInterval(_, r)) => {
+                let (result, overflow) = fit_to_bounds(interval_arithmetic(op, *l, *r)?, &uint_bounds(*ty), mode);
+                Ok((AbstractValue::UintInterval(*ty, result), overflow))
+            }
+            _ => Err(Error::new(ErrorKind::NotImplementedError)),
+        }
+    }
+
+    /// Negates a signed integer value. Returns the result according to the overflow mode along
+    /// with an abstract flag that indicates whether the negation overflows (i.e. negates MIN).
+    pub fn negate(&self, mode: OverflowMode) -> Result<(Self, booleans::AbstractBool), Error> {
+        match self {
+            AbstractValue::IntInterval(ty, i) => {
+                let (result, overflow) = fit_to_bounds(-*i, &int_bounds(*ty), mode);
+                Ok((AbstractValue::IntInterval(*ty, result), overflow))
+            }
             _ => Err(Error::new(ErrorKind::NotImplementedError)),
         }
     }
@@ -115,3 +455,117 @@ impl AbstractValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::booleans::AbstractBool;
+    use crate::domains::interval::Interval;
+
+    #[test]
+    fn arithmetic_respects_type_bounds() {
+        let a = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(200, 250));
+        let b = AbstractValue::UintInterval(UintTy::U8, Interval::from(10));
+
+        // Part of the result overflows, so wrapping can produce any u8
+        assert_eq!(
+            a.arithmetic(&BinOp::Add, &b, OverflowMode::Wrap).unwrap(),
+            (AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255)), AbstractBool::Top),
+        );
+        // Only the results that don't overflow are produced when overflow panics
+        assert_eq!(
+            a.arithmetic(&BinOp::Add, &b, OverflowMode::Panic).unwrap(),
+            (AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(210, 255)), AbstractBool::Top),
+        );
+        // The whole result underflows by the same amount, so it wraps precisely
+        assert_eq!(
+            b.arithmetic(&BinOp::Sub, &a, OverflowMode::Wrap).unwrap(),
+            (AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(16, 66)), AbstractBool::True),
+        );
+
+        let c = AbstractValue::IntInterval(IntTy::I8, Interval::from_interval(-128, -100));
+        assert_eq!(
+            c.negate(OverflowMode::Panic).unwrap(),
+            (AbstractValue::IntInterval(IntTy::I8, Interval::from_interval(100, 127)), AbstractBool::Top),
+        );
+    }
+
+    #[test]
+    fn top_uses_type_bounds() {
+        let v = AbstractValue::IntInterval(IntTy::I16, Interval::from(3));
+        assert_eq!(v.top(), AbstractValue::IntInterval(IntTy::I16, Interval::from_interval(-32768, 32767)));
+        assert_eq!(int_from_bits(IntTy::I16, 0xffff), -1);
+    }
+
+    #[test]
+    fn certain_overflow_panics_to_bottom() {
+        let a = AbstractValue::UintInterval(UintTy::U8, Interval::from(255));
+        let b = AbstractValue::UintInterval(UintTy::U8, Interval::from(1));
+        let (result, overflow) = a.arithmetic(&BinOp::Add, &b, OverflowMode::Panic).unwrap();
+        assert!(result.is_bottom());
+        assert_eq!(overflow, AbstractBool::True);
+
+        // Bottom operands produce bottom results without overflowing
+        let (result, overflow) = result.arithmetic(&BinOp::Sub, &b, OverflowMode::Wrap).unwrap();
+        assert!(result.is_bottom());
+        assert_eq!(overflow, AbstractBool::Bot);
+        assert!(AbstractValue::Tuple(vec![b.clone(), result]).is_bottom());
+    }
+
+    #[test]
+    fn lattice_operations_on_tuples() {
+        let a = AbstractValue::Tuple(vec![
+            AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 10)),
+            AbstractValue::Bool(AbstractBool::Top),
+        ]);
+        let b = AbstractValue::Tuple(vec![
+            AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(5, 20)),
+            AbstractValue::Bool(AbstractBool::True),
+        ]);
+        assert_eq!(a.meet(&b), AbstractValue::Tuple(vec![
+            AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(5, 10)),
+            AbstractValue::Bool(AbstractBool::True),
+        ]));
+        assert!(a.meet(&b).leq(&a));
+        assert!(!a.leq(&b));
+        assert!(a.leq(&a.join(&b)));
+    }
+
+    #[test]
+    fn integer_values_are_a_lattice() {
+        use crate::domains::laws::{check_lattice_laws, check_monotone_binary, random_interval, random_unsigned_interval};
+
+        // Values always lie within the bounds of their type
+        let unsigned = |rng: &mut _| AbstractValue::UintInterval(UintTy::U8, random_unsigned_interval(rng).meet(&uint_bounds(UintTy::U8)));
+        let signed = |rng: &mut _| AbstractValue::IntInterval(IntTy::I8, random_interval(rng).meet(&int_bounds(IntTy::I8)));
+        check_lattice_laws(unsigned);
+        check_lattice_laws(signed);
+
+        for op in [BinOp::Add, BinOp::Sub, BinOp::Mul, BinOp::Div, BinOp::Rem] {
+            for mode in [OverflowMode::Wrap, OverflowMode::Panic] {
+                check_monotone_binary(unsigned, |a, b| a.arithmetic(&op, b, mode).unwrap().0);
+                check_monotone_binary(signed, |a, b| a.arithmetic(&op, b, mode).unwrap().0);
+            }
+        }
+    }
+
+    #[test]
+    fn narrowing_refines_type_bounds() {
+        // A value that was widened up to the maximum of its type
+        let widened = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255));
+        let iterate = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 10));
+        assert_eq!(widened.narrow(&iterate), iterate);
+
+        let widened = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(3, 255));
+        assert_eq!(widened.narrow(&iterate), AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(3, 10)));
+    }
+
+    #[test]
+    fn decode_constant_bytes() {
+        let bytes = [0x34, 0x12, 0, 0];
+        assert_eq!(bits_from_bytes(&bytes, smir::target::Endian::Little), 0x1234);
+        assert_eq!(bits_from_bytes(&bytes, smir::target::Endian::Big), 0x34120000);
+        // -2 as an i16
+        assert_eq!(int_from_bits(IntTy::I16, bits_from_bytes(&[0xfe, 0xff], smir::target::Endian::Little)), -2);
+    }
+}
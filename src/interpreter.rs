@@ -11,25 +11,33 @@ extern crate stable_mir as smir;
 // The rustc_smir::run macro currently expects stable_mir to be in the namespace
 extern crate stable_mir;
 
-use crate::domains::{AbstractFunction, AbstractValue, booleans, interval};
+use crate::alarms::{Alarm, AlarmKind, PanicSite, Reachability, Verdict};
+use crate::conditions::{Comparison, Condition, ConditionTracker};
+use crate::diagnostics;
+use crate::report::{self, OutputFormat};
+use crate::domains::{AbstractDomain, AbstractFunction, AbstractValue, CallContext, OverflowMode, array_length, int_from_bits, length_bounds};
 use crate::errors::*;
-use crate::mir_helpers::get_fn_types;
+use crate::lints::{Lint, LintKind};
+use crate::testgen::{self, Branch, TestCase};
+use crate::mir_helpers::{bottom_up_order, get_callee_name, get_fn_types, get_loop_heads, get_successors, is_panic_fn, mutably_borrowed_locals};
 use log::debug;
 use rustc_errors::registry;
 use rustc_hash::{FxHashMap, FxHashSet};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, CRATE_DEF_ID};
+use rustc_middle::ty::print::{with_crate_prefix, with_no_trimmed_paths};
 use rustc_session::config::{self, CheckCfg};
 use rustc_smir::{run, rustc_internal};
 use smir::{CrateDef};
-use smir::ty::{TyKind, RigidTy};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::{process, str};
-use crate::domains::AbstractValue::IntInterval;
 use crate::domains::booleans::AbstractBool;
-use crate::domains::interval::Interval;
+use crate::domains::interval::{Integer, Interval, IntervalElem};
 
-fn get_sysroot() -> String {
+/// Returns the sysroot of the `rustc` on the path, which has to be passed to the compiler when
+/// running the analysis from a binary outside of the toolchain.
+pub fn get_sysroot() -> String {
     let out = process::Command::new("rustc")
         .arg("--print=sysroot")
         .current_dir(".")
@@ -41,32 +49,395 @@ fn get_sysroot() -> String {
 
 type Summary = AbstractFunction;
 
-fn check_summaries(summaries: HashMap<String, Summary>) {
-    // TODO(klinvill): placeholder
-    println!("Analysis results: ");
-    for (func, result) in &summaries {
-        println!("{func:?}: {result:?}");
+/// Default for the number of call contexts kept per function, see `Summaries`.
+pub const DEFAULT_CONTEXT_LIMIT: usize = 4;
+
+/// Summaries of the functions analyzed so far, by function name.
+///
+/// Besides the general summary of each function, which covers any input, up to `context_limit`
+/// more precise summaries are computed on demand for the abstract arguments seen at call sites.
+/// Once a function has that many contexts, further calls with new arguments use the general
+/// summary.
+///
+/// Only calls made on the final pass over a body are recorded as contexts. The arguments seen
+/// before the fixpoint is reached (e.g. a loop counter before and after widening) are transient,
+/// so the results for them are cached separately and don't count towards the limit.
+struct Summaries {
+    summaries: HashMap<String, Summary>,
+    /// Local functions whose bodies can be analyzed for new contexts.
+    items: HashMap<String, smir::CrateItem>,
+    /// Functions whose bodies are currently being analyzed for a context. Calls back into them
+    /// (i.e. recursion) use the general summary so the analysis terminates.
+    in_progress: HashSet<String>,
+    context_limit: usize,
+    /// Whether calls are currently made with the final arguments of their call sites.
+    record_contexts: bool,
+    /// Results of calls with transient arguments, by function name.
+    transient: HashMap<String, Vec<CallContext>>,
+}
+
+impl Summaries {
+    fn new() -> Self {
+        Self::with_items(&[], DEFAULT_CONTEXT_LIMIT)
+    }
+
+    fn with_items(items: &[smir::CrateItem], context_limit: usize) -> Self {
+        Summaries {
+            summaries: HashMap::new(),
+            items: items.iter().map(|item| (item.name(), item.clone())).collect(),
+            in_progress: HashSet::new(),
+            context_limit,
+            record_contexts: true,
+            transient: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, name: String, summary: Summary) {
+        self.summaries.insert(name, summary);
+    }
+
+    /// Returns the abstract value that a call to the named function with the given abstract
+    /// arguments returns, analyzing the function for the arguments if they haven't been seen
+    /// before. Returns None if there is no summary for the function.
+    fn call(&mut self, name: &str, arguments: &[AbstractValue]) -> Option<AbstractValue> {
+        let summary = self.summaries.get(name)?;
+        if arguments.len() != summary.arguments.len() {
+            return None;
+        }
+        if arguments.iter().any(|arg| arg.is_bottom()) {
+            return Some(summary.return_val.bottom());
+        }
+        if let Some(return_val) = summary.context(arguments) {
+            return Some(return_val.clone());
+        }
+        let record = self.record_contexts;
+        if !record {
+            let cached = self.transient.get(name).into_iter().flatten().find(|context| context.arguments == arguments);
+            if let Some(context) = cached {
+                return Some(context.return_val.clone());
+            }
+        }
+        let general = summary.return_val.clone();
+        if summary.contexts.len() >= self.context_limit || self.in_progress.contains(name) {
+            return Some(general);
+        }
+        let item = match self.items.get(name) {
+            Some(item) => item.clone(),
+            None => return Some(general),
+        };
+
+        self.in_progress.insert(name.to_string());
+        let analysis = interpret_body(&item.body(), &arguments.to_vec(), self);
+        self.in_progress.remove(name);
+        let return_val = match analysis {
+            Ok(analysis) => return_value(&analysis, &general),
+            Err(_) => general,
+        };
+
+        let context = CallContext { arguments: arguments.to_vec(), return_val: return_val.clone() };
+        if !record {
+            self.transient.entry(name.to_string()).or_default().push(context);
+        } else if let Some(summary) = self.summaries.get_mut(name) {
+            summary.contexts.push(context);
+        }
+        Some(return_val)
     }
 }
 
-pub fn analyze_program<B>(_tcx: rustc_middle::ty::TyCtxt) -> std::ops::ControlFlow<B> {
-    let mut abstract_fns = HashMap::new();
+fn check_summaries(tcx: rustc_middle::ty::TyCtxt, analysis: &ProgramAnalysis, format: OutputFormat) {
+    for (func, result) in &analysis.summaries {
+        debug!("Summary of {func}: {result:?}");
+    }
+    match format {
+        OutputFormat::Diagnostics => {
+            // Sorted so the diagnostics come out in the same order on every run
+            let mut functions: Vec<&String> = analysis.alarms.keys().chain(analysis.lints.keys()).collect();
+            functions.sort();
+            functions.dedup();
+            for func in functions {
+                for alarm in analysis.alarms.get(func).into_iter().flatten() {
+                    diagnostics::emit_alarm(tcx, func, alarm);
+                }
+                for lint in analysis.lints.get(func).into_iter().flatten() {
+                    diagnostics::emit_lint(tcx, func, lint);
+                }
+            }
+        }
+        OutputFormat::Json => println!("{}", report::to_json(analysis)),
+        OutputFormat::Sarif => println!("{}", report::to_sarif(analysis)),
+        OutputFormat::Tests => print!("{}", testgen::to_rust_tests(&analysis.tests, &analysis.call_paths)),
+    }
+}
+
+/// Results of analyzing every function of the local crate, by function name.
+#[derive(Debug)]
+pub struct ProgramAnalysis {
+    pub summaries: HashMap<String, AbstractFunction>,
+    pub alarms: HashMap<String, Vec<Alarm>>,
+    pub invariants: HashMap<String, Vec<Invariant>>,
+    /// Calls to panic functions, classified by whether they can be reached.
+    pub panics: HashMap<String, Vec<PanicSite>>,
+    /// Dead code and constant branch conditions.
+    pub lints: HashMap<String, Vec<Lint>>,
+    /// Test inputs at the boundaries of each branch, for functions whose arguments we can write
+    /// literals for.
+    pub tests: HashMap<String, Vec<TestCase>>,
+    /// Paths through which generated tests call each function, e.g. `crate::parse::digit`.
+    /// Functions that can't be called by path from a test module in the crate root (methods,
+    /// generic functions and private functions of nested modules) have none.
+    pub call_paths: HashMap<String, String>,
+}
+
+/// Abstract values of the locals on entry to a reachable basic block, which hold for every
+/// execution that reaches the block.
+#[derive(Debug, Clone)]
+pub struct Invariant {
+    pub block: smir::mir::BasicBlockIdx,
+    /// Span of the first statement of the block, or of its terminator if it has no statements.
+    pub span: smir::ty::Span,
+    pub values: BTreeMap<smir::mir::Local, AbstractValue>,
+}
+
+impl ProgramAnalysis {
+    pub fn alarm_count(&self) -> usize {
+        self.alarms.values().map(Vec::len).sum()
+    }
+}
+
+/// Analyzes every function of the local crate. Has to be called from within the callback of
+/// `run!`, like `analyze_program`.
+pub fn analyze_crate() -> ProgramAnalysis {
+    analyze_crate_with(DEFAULT_CONTEXT_LIMIT)
+}
+
+/// Like `analyze_crate`, keeping at most `context_limit` call contexts per function.
+pub fn analyze_crate_with(context_limit: usize) -> ProgramAnalysis {
+    let mut alarms = HashMap::new();
+    let mut invariants = HashMap::new();
+    let mut panics = HashMap::new();
+    let mut lints = HashMap::new();
+    let mut tests = HashMap::new();
+    let mut call_paths = HashMap::new();
     let all_items: Vec<smir::CrateItem> = smir::all_local_items();
-    for item in all_items {
-        println!("Checking function: {}", item.name());
-        println!("Has kind: {:?}", item.kind());
-        match analyze_function(&item.body()) {
-            Ok(abstract_fn) => {
+    let mut abstract_fns = Summaries::with_items(&all_items, context_limit);
+    // Callees are analyzed before their callers so that their summaries are available at call
+    // sites. Calls within a recursion cycle to a function that hasn't been summarized yet fall
+    // back to the top value of the return type.
+    for item in bottom_up_order(&all_items) {
+        debug!("Checking function: {}", item.name());
+        debug!("Has kind: {:?}", item.kind());
+        let body = item.body();
+        match analyze_function(&body, &mut abstract_fns) {
+            Ok((abstract_fn, analysis)) => {
                 abstract_fns.insert(item.name(), abstract_fn);
+                invariants.insert(item.name(), block_invariants(&body, &analysis));
+                panics.insert(item.name(), panic_sites(&body, &analysis));
+                lints.insert(item.name(), dead_code_lints(&body, &analysis));
+                if let Some(cases) = boundary_tests(&body, &analysis) {
+                    tests.insert(item.name(), cases);
+                }
+                if let Some(path) = call_path(&item) {
+                    call_paths.insert(item.name(), path);
+                }
+                alarms.insert(item.name(), analysis.alarms);
                 ()
             }
             _ => (),
         };
     };
 
-    check_summaries(abstract_fns);
+    ProgramAnalysis { summaries: abstract_fns.summaries, alarms, invariants, panics, lints, tests, call_paths }
+}
 
-    std::ops::ControlFlow::Continue(())
+/// Returns the absolute path of the function, e.g. `crate::parse::digit`, if it is a free
+/// function without type or const parameters that is visible from the crate root.
+fn call_path(item: &smir::CrateItem) -> Option<String> {
+    let def_id = rustc_internal::internal(item.0);
+    rustc_middle::ty::tls::with(|tcx| {
+        let callable = tcx.def_kind(def_id) == DefKind::Fn
+            && !tcx.generics_of(def_id).requires_monomorphization(tcx)
+            && tcx.visibility(def_id).is_accessible_from(CRATE_DEF_ID, tcx);
+        callable.then(|| with_no_trimmed_paths!(with_crate_prefix!(tcx.def_path_str(def_id))))
+    })
+}
+
+fn block_invariants(body: &smir::mir::Body, analysis: &BodyAnalysis) -> Vec<Invariant> {
+    body.blocks.iter()
+        .zip(&analysis.entry_states)
+        .enumerate()
+        .filter_map(|(bb, (block, state))| {
+            let span = block.statements.first().map_or(block.terminator.span, |statement| statement.span);
+            state.as_ref().map(|state| Invariant {
+                block: bb,
+                span,
+                values: state.iter().map(|(local, value)| (*local, value.clone())).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Classifies the calls to panic functions in the body. A call is unreachable if its block was
+/// never reached. It is reachable if its block lies on the path that starts at the entry block and
+/// follows blocks with a single feasible successor, as long as leaving each block can't stop the
+/// execution: the path only continues through gotos, switches and checks that are proven to hold.
+/// Calls and drops end the path, since the code they run may panic or never return.
+fn panic_sites(body: &smir::mir::Body, analysis: &BodyAnalysis) -> Vec<PanicSite> {
+    let mut must_reach = HashSet::new();
+    let mut bb = 0;
+    while analysis.entry_states.get(bb).is_some_and(Option::is_some) && must_reach.insert(bb) {
+        let continues = match &body.blocks[bb].terminator.kind {
+            smir::mir::TerminatorKind::Goto { .. } | smir::mir::TerminatorKind::SwitchInt { .. } => true,
+            smir::mir::TerminatorKind::Assert { cond, expected, .. } => {
+                let holds = AbstractValue::Bool(AbstractBool::from(*expected));
+                analysis.exit_states[bb].as_ref().and_then(|exit| interpret_operand(cond, exit).ok()) == Some(holds)
+            }
+            _ => false,
+        };
+        match analysis.successors[bb][..] {
+            [(succ, _)] if continues => bb = succ,
+            _ => break,
+        }
+    }
+
+    body.blocks.iter()
+        .enumerate()
+        .filter_map(|(bb, block)| {
+            let callee = match &block.terminator.kind {
+                smir::mir::TerminatorKind::Call { func, .. } => get_callee_name(func).filter(|name| is_panic_fn(name))?,
+                _ => return None,
+            };
+            let reachability = if analysis.entry_states[bb].is_none() {
+                Reachability::Unreachable
+            } else if must_reach.contains(&bb) {
+                Reachability::Reachable
+            } else {
+                Reachability::Unknown
+            };
+            Some(PanicSite { callee, reachability, span: block.terminator.span })
+        })
+        .collect()
+}
+
+/// Finds dead code and constant branch conditions in the body.
+///
+/// Only the first block of a dead region is reported, i.e. dead blocks with a live predecessor.
+/// Blocks that can only be entered through unwinding (cleanup blocks) are never reached by the
+/// analysis, so they are skipped. Dead paths to a panic function are skipped as well since
+/// `panic_sites` already covers them, and so are the conditions that guard them: an `assert!` that
+/// always holds is neither dead code nor a constant condition worth reporting. Conditions are only
+/// reported for boolean switches on a local, which excludes literal conditions like `while true`.
+fn dead_code_lints(body: &smir::mir::Body, analysis: &BodyAnalysis) -> Vec<Lint> {
+    let is_live = |bb: smir::mir::BasicBlockIdx| analysis.entry_states[bb].is_some();
+    let mut lints = Vec::new();
+    let mut reported = HashSet::new();
+    for (bb, block) in body.blocks.iter().enumerate() {
+        if !is_live(bb) {
+            continue;
+        }
+        for succ in get_successors(&block.terminator) {
+            if !is_live(succ) && !leads_to_panic(body, succ) && reported.insert(succ) {
+                let succ_block = &body.blocks[succ];
+                let span = succ_block.statements.first().map_or(succ_block.terminator.span, |statement| statement.span);
+                lints.push(Lint { kind: LintKind::DeadCode, span });
+            }
+        }
+
+        let smir::mir::TerminatorKind::SwitchInt { discr: discr @ (smir::mir::Operand::Copy(_) | smir::mir::Operand::Move(_)), targets } = &block.terminator.kind else {
+            continue;
+        };
+        let Some(exit) = &analysis.exit_states[bb] else { continue };
+        let value = match interpret_operand(discr, exit) {
+            Ok(AbstractValue::Bool(AbstractBool::True)) => true,
+            Ok(AbstractValue::Bool(AbstractBool::False)) => false,
+            _ => continue,
+        };
+        let takes_otherwise = targets.branches().all(|(branch, _)| branch != value as u128);
+        let mut dead_targets = targets.branches()
+            .filter(|(branch, _)| *branch != value as u128)
+            .map(|(_, target)| target)
+            .chain((!takes_otherwise).then(|| targets.otherwise()));
+        if dead_targets.any(|target| leads_to_panic(body, target)) {
+            continue;
+        }
+        lints.push(Lint { kind: LintKind::ConstantCondition(value), span: block.terminator.span });
+    }
+    lints
+}
+
+/// Returns true if every path from the block ends in a call to a panic function. Only straight-line
+/// code is followed, which covers the blocks that build the panic message of e.g. `assert!(x < 10,
+/// "x is {}", x)` before calling the panic function.
+fn leads_to_panic(body: &smir::mir::Body, mut bb: smir::mir::BasicBlockIdx) -> bool {
+    for _ in 0..body.blocks.len() {
+        let terminator = &body.blocks[bb].terminator;
+        if let smir::mir::TerminatorKind::Call { func, .. } = &terminator.kind {
+            if get_callee_name(func).is_some_and(|name| is_panic_fn(&name)) {
+                return true;
+            }
+        }
+        match get_successors(terminator)[..] {
+            [succ] => bb = succ,
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Derives test inputs at the boundaries of the arguments on entry to the function, and of the
+/// arguments reaching each feasible side of every branch. Arguments that are reassigned before a
+/// branch may hold other values at the branch than on entry, so inputs derived from such branches
+/// may not reach the branch. Returns None if the function takes arguments we can't write literals
+/// for.
+fn boundary_tests(body: &smir::mir::Body, analysis: &BodyAnalysis) -> Option<Vec<TestCase>> {
+    let arg_count = body.arg_locals().len();
+    let mut edges: Vec<(Option<Branch>, &State)> = analysis.entry_states.first()
+        .and_then(Option::as_ref)
+        .map(|entry| (None, entry))
+        .into_iter()
+        .collect();
+    for (bb, block) in body.blocks.iter().enumerate() {
+        if let smir::mir::TerminatorKind::SwitchInt { .. } = block.terminator.kind {
+            edges.extend(analysis.successors[bb].iter().map(|(target, state)| (Some(Branch { block: bb, target: *target }), state)));
+        }
+    }
+
+    let mut tests: Vec<TestCase> = Vec::new();
+    for (branch, state) in edges {
+        let arguments: Vec<AbstractValue> = (1..=arg_count)
+            .map(|local| state.get(&local).cloned())
+            .collect::<Option<_>>()?;
+        for arguments in testgen::boundary_arguments(&arguments)? {
+            if !tests.iter().any(|test| test.arguments == arguments) {
+                tests.push(TestCase { branch, arguments });
+            }
+        }
+    }
+    Some(tests)
+}
+
+/// Analyzes every function of the local crate and reports the results. Breaks with the number of
+/// alarms if any were found, so callers of `run!` can tell a crate with alarms apart from a clean
+/// one.
+pub fn analyze_program(tcx: rustc_middle::ty::TyCtxt) -> std::ops::ControlFlow<usize> {
+    analyze_program_with(tcx, OutputFormat::Diagnostics)
+}
+
+/// Like `analyze_program`, but reports the results in the given format.
+pub fn analyze_program_with(tcx: rustc_middle::ty::TyCtxt, format: OutputFormat) -> std::ops::ControlFlow<usize> {
+    report_analysis(tcx, &analyze_crate(), format)
+}
+
+/// Reports the results of `analyze_crate` in the given format. Breaks with the number of alarms
+/// if any were found, like `analyze_program`.
+pub fn report_analysis(tcx: rustc_middle::ty::TyCtxt, analysis: &ProgramAnalysis, format: OutputFormat) -> std::ops::ControlFlow<usize> {
+    let alarm_count = analysis.alarm_count();
+    check_summaries(tcx, analysis, format);
+
+    if alarm_count > 0 {
+        std::ops::ControlFlow::Break(alarm_count)
+    } else {
+        std::ops::ControlFlow::Continue(())
+    }
 }
 
 fn is_tuple(ty: &smir::ty::Ty) -> bool {
@@ -81,7 +452,11 @@ fn tuple_fields(ty: &smir::ty::Ty) -> Result<Vec<smir::ty::Ty>, Error> {
     }
 }
 
-fn can_interpret(local_decls: &[&smir::mir::LocalDecl]) -> bool {
+/// Returns true if the arguments and the return value of the function can be abstracted. Other
+/// locals don't have to be: locals of unsupported types (e.g. the `fmt::Arguments` that `panic!`
+/// builds) never get a value in the state, so statements that read them fail and havoc their
+/// destination instead.
+fn can_interpret(body: &smir::mir::Body) -> bool {
     fn is_numeric(ty: &smir::ty::Ty) -> bool {
         match ty.kind() {
             smir::ty::TyKind::RigidTy(smir::ty::RigidTy::Int(_)) |
@@ -99,17 +474,26 @@ fn can_interpret(local_decls: &[&smir::mir::LocalDecl]) -> bool {
         }
     }
 
-    fn is_numeric_or_bool(ty: &smir::ty::Ty) -> bool {
-        is_numeric(ty) || is_bool(ty)
+    // The destination of a diverging call, e.g. of `panic!`
+    fn is_never(ty: &smir::ty::Ty) -> bool {
+        matches!(ty.kind(), smir::ty::TyKind::RigidTy(smir::ty::RigidTy::Never))
     }
 
-    local_decls.iter().map(|decl| decl.ty).all(|ty| {
-        is_numeric_or_bool(&ty)
-            || (is_tuple(&ty) && tuple_fields(&ty).unwrap().iter().all(|tyf| is_numeric_or_bool(&tyf)))
-    })
+    fn is_supported(ty: &smir::ty::Ty) -> bool {
+        match ty.kind() {
+            smir::ty::TyKind::RigidTy(smir::ty::RigidTy::Tuple(fields)) => fields.iter().all(is_supported),
+            smir::ty::TyKind::RigidTy(smir::ty::RigidTy::Array(elem_ty, _)) |
+            smir::ty::TyKind::RigidTy(smir::ty::RigidTy::Slice(elem_ty)) |
+            smir::ty::TyKind::RigidTy(smir::ty::RigidTy::Ref(_, elem_ty, _))
+            => is_supported(&elem_ty),
+            _ => is_numeric(ty) || is_bool(ty) || is_never(ty),
+        }
+    }
+
+    std::iter::once(body.ret_local()).chain(body.arg_locals()).all(|decl| is_supported(&decl.ty))
 }
 
-fn analyze_function(function: &smir::mir::Body) -> Result<AbstractFunction, Error> {
+fn analyze_function(function: &smir::mir::Body, summaries: &mut Summaries) -> Result<(AbstractFunction, BodyAnalysis), Error> {
     debug!("{function:#?}");
 
     let (arg_types, return_type) = get_fn_types(function);
@@ -117,41 +501,69 @@ fn analyze_function(function: &smir::mir::Body) -> Result<AbstractFunction, Erro
     debug!("Argument types: {arg_types:?}");
     debug!("Return type: {return_type:?}");
 
-    let local_decls: Vec<&smir::mir::LocalDecl> = function.locals().iter().collect();
-    if can_interpret(&local_decls) {
-        let abstract_fn = interpret_intervals(function);
+    if can_interpret(function) {
+        let (abstract_fn, analysis) = interpret_intervals(function, summaries)?;
         debug!("Abstract function: {abstract_fn:?}\n");
-        let state = interpret_body(function, &vec![IntInterval(Interval::from(3))]);
-        debug!("State: {state:?}\n");
-        abstract_fn
+        debug!("Analysis: {analysis:?}\n");
+        Ok((abstract_fn, analysis))
     } else {
         debug!("\n");
         Err(Error::new(ErrorKind::InterpreterError))
     }
 }
 
-fn interpret_intervals(function: &smir::mir::Body) -> Result<AbstractFunction, Error> {
+/// Computes the general summary of a function by interpreting its body from arguments that cover
+/// every possible input. The alarms found along the way can be raised by any call to the function.
+fn interpret_intervals(function: &smir::mir::Body, summaries: &mut Summaries) -> Result<(AbstractFunction, BodyAnalysis), Error> {
     let (arg_types, return_type) = get_fn_types(function);
 
     // TODO(klinvill): We only keep the first error here. Should we instead be keeping track of all errors?
-    let abstract_args: Result<Vec<_>, _> = arg_types.iter().map(AbstractValue::new).collect();
-    let abstract_return = AbstractValue::new(&return_type);
+    let arguments: Vec<AbstractValue> = arg_types.iter().map(AbstractValue::new).collect::<Result<_, _>>()?;
+    // Functions returning `!` never return, so calls to them never return either
+    let declared_return = match return_type.kind() {
+        smir::ty::TyKind::RigidTy(smir::ty::RigidTy::Never) => AbstractValue::Uninit,
+        _ => AbstractValue::new(&return_type)?,
+    };
 
-    match (abstract_args, abstract_return) {
-        (Ok(arguments), Ok(return_val)) => Ok(AbstractFunction {
-            arguments,
-            return_val,
-        }),
-        (Err(e), _) => Err(e),
-        (_, Err(e)) => Err(e),
-    }
+    let analysis = interpret_body(function, &arguments, summaries)?;
+    let return_val = return_value(&analysis, &declared_return);
+    let abstract_fn = AbstractFunction {
+        arguments,
+        return_val,
+        contexts: vec![],
+    };
+    Ok((abstract_fn, analysis))
 }
 
-fn interpret_body(body: &smir::mir::Body, arg_values: &Vec<AbstractValue>) -> Result<HashMap<smir::mir::Local, AbstractValue>, Error> {
-    let mut state: HashMap<smir::mir::Local, AbstractValue> = HashMap::new();
-    let mut errors = Vec::new();
+/// Number of times the entry state of a block may be updated while narrowing after the fixpoint is
+/// reached. Narrowing intervals stabilizes well before that; the bound only guarantees termination.
+const MAX_NARROWING_VISITS: usize = 20;
+
+/// Abstract state at a program point, mapping each local to its abstract value. Locals that have
+/// not been assigned yet are absent from the map.
+type State = HashMap<smir::mir::Local, AbstractValue>;
 
-    let (arg_types, return_type) = get_fn_types(body);
+/// Abstract states of a function body once the analysis has reached a fixpoint.
+#[derive(Debug)]
+struct BodyAnalysis {
+    /// Abstract state on entry to each basic block, indexed by block. A block whose state is `None`
+    /// was never reached during the analysis.
+    entry_states: Vec<Option<State>>,
+    /// Abstract state after the statements of each basic block have executed, right before its
+    /// terminator. `None` for blocks that were never reached.
+    exit_states: Vec<Option<State>>,
+    /// Join of the states at every reachable `Return` terminator. `None` if the function can never
+    /// return normally.
+    return_state: Option<State>,
+    /// Checks in the body that may fail.
+    alarms: Vec<Alarm>,
+    /// Successors of each basic block that are feasible from its final entry state, along with the
+    /// state on entry to each of them along that edge. Empty for blocks that were never reached.
+    successors: Vec<Vec<(smir::mir::BasicBlockIdx, State)>>,
+}
+
+fn interpret_body(body: &smir::mir::Body, arg_values: &Vec<AbstractValue>, summaries: &mut Summaries) -> Result<BodyAnalysis, Error> {
+    let (arg_types, _) = get_fn_types(body);
     if arg_values.len() != arg_types.len() {
         return Err(Error::with_message(
             ErrorKind::InvalidArgumentError,
@@ -159,161 +571,1467 @@ fn interpret_body(body: &smir::mir::Body, arg_values: &Vec<AbstractValue>) -> Re
         ));
     }
 
-    // Insert arguments into state map
+    // Insert arguments into the state of the entry block
+    let mut initial_state = State::new();
     for (i, arg) in arg_values.iter().enumerate() {
-        state.insert(i + 1, arg.clone());
+        initial_state.insert(i + 1, arg.clone());
     }
 
+    let mut entry_states: Vec<Option<State>> = vec![None; body.blocks.len()];
+    let mut exit_states: Vec<Option<State>> = vec![None; body.blocks.len()];
+    let mut errors: Vec<Vec<Error>> = body.blocks.iter().map(|_| Vec::new()).collect();
+    let mut block_alarms: Vec<Option<Alarm>> = vec![None; body.blocks.len()];
+    let mut successors: Vec<Vec<(smir::mir::BasicBlockIdx, State)>> = vec![Vec::new(); body.blocks.len()];
+    if body.blocks.is_empty() {
+        return Ok(BodyAnalysis { entry_states, exit_states, return_state: None, alarms: vec![], successors: vec![] });
+    }
+    entry_states[0] = Some(initial_state.clone());
+
+    let loop_heads = get_loop_heads(body);
+    // Call contexts are only recorded on the final pass, once the arguments at call sites are
+    // stable. The final pass of a body that is itself analyzed for a transient context doesn't
+    // record them either.
+    let record_contexts = summaries.record_contexts;
+    summaries.record_contexts = false;
+    // Blocks are processed in index order, which roughly follows the order rustc lays out MIR
+    // blocks in and keeps the number of times each block is revisited low.
+    let mut worklist = BTreeSet::from([0]);
+
+    while let Some(bb) = worklist.pop_first() {
+        let entry = entry_states[bb].as_ref().expect("Only reached blocks are added to the worklist");
+        let visit = visit_block(bb, body, entry, summaries);
+
+        for (succ, succ_state) in visit.successors {
+            let new_state = match &entry_states[succ] {
+                None => succ_state,
+                // Nothing new reaches the successor, so it doesn't need to be revisited.
+                Some(old) if state_leq(&succ_state, old) => continue,
+                // Widening at loop heads guarantees that the analysis terminates, even for
+                // domains like intervals that have infinite ascending chains.
+                Some(old) if loop_heads.contains(&succ) => widen_states(old, &join_states(old, &succ_state)),
+                Some(old) => join_states(old, &succ_state),
+            };
+            entry_states[succ] = Some(new_state);
+            worklist.insert(succ);
+        }
+    }
+
+    // Widening may overshoot the fixpoint, e.g. a loop counter bounded by the loop condition is
+    // widened up to the maximum of its type. Descending iterations, narrowing at loop heads,
+    // recover some of that precision while staying above the least fixpoint. They follow the
+    // worklist order like the ascending ones, so a refined state reaches the end of a loop-exit
+    // path of any length instead of moving one block per iteration.
+    let mut edges: HashMap<(smir::mir::BasicBlockIdx, smir::mir::BasicBlockIdx), State> = HashMap::new();
+    let mut predecessors: Vec<BTreeSet<smir::mir::BasicBlockIdx>> = vec![BTreeSet::new(); body.blocks.len()];
     for (bb, block) in body.blocks.iter().enumerate() {
-        let result = interpret_block(block, &mut state);
-        match result {
-            Err(e) => errors.push(e),
-            _ => (),
+        for succ in get_successors(&block.terminator) {
+            predecessors[succ].insert(bb);
+        }
+        if let Some(entry) = &entry_states[bb] {
+            record_edges(bb, visit_block(bb, body, entry, summaries).successors, &mut edges);
+        }
+    }
+
+    let mut narrowing_visits = vec![0; body.blocks.len()];
+    let mut worklist: BTreeSet<smir::mir::BasicBlockIdx> = (0..body.blocks.len()).collect();
+    while let Some(bb) = worklist.pop_first() {
+        let incoming = predecessors[bb].iter()
+            .filter_map(|pred| edges.get(&(*pred, bb)))
+            .chain((bb == 0).then_some(&initial_state))
+            .fold(None, |acc: Option<State>, state| match acc {
+                None => Some(state.clone()),
+                Some(acc) => Some(join_states(&acc, state)),
+            });
+        let new_state = match (&entry_states[bb], incoming) {
+            (Some(old), Some(new)) if loop_heads.contains(&bb) => Some(narrow_states(old, &new)),
+            (_, new) => new,
+        };
+        if new_state == entry_states[bb] || narrowing_visits[bb] == MAX_NARROWING_VISITS {
+            continue;
+        }
+        narrowing_visits[bb] += 1;
+        entry_states[bb] = new_state;
+
+        edges.retain(|(pred, _), _| *pred != bb);
+        if let Some(entry) = &entry_states[bb] {
+            record_edges(bb, visit_block(bb, body, entry, summaries).successors, &mut edges);
+        }
+        worklist.extend(get_successors(&body.blocks[bb].terminator));
+    }
+
+    // The exit states, errors, alarms and feasible successors are those of the final entry states.
+    summaries.record_contexts = record_contexts;
+    for (bb, entry) in entry_states.iter().enumerate() {
+        if let Some(entry) = entry {
+            let visit = visit_block(bb, body, entry, summaries);
+            exit_states[bb] = Some(visit.exit);
+            errors[bb] = visit.errors;
+            block_alarms[bb] = visit.alarm;
+            successors[bb] = visit.successors;
         }
     }
+
+    let return_state = body.blocks.iter()
+        .zip(&exit_states)
+        .filter(|(block, _)| matches!(block.terminator.kind, smir::mir::TerminatorKind::Return))
+        .filter_map(|(_, state)| state.as_ref())
+        .fold(None, |acc: Option<State>, state| match acc {
+            None => Some(state.clone()),
+            Some(acc) => Some(join_states(&acc, state)),
+        });
+
+    let alarms = block_alarms.into_iter().flatten().collect();
+
     debug!("Errors while interpreting body: {errors:#?}");
-    Ok(state)
+    Ok(BodyAnalysis { entry_states, exit_states, return_state, alarms, successors })
+}
+
+/// Result of interpreting a single basic block from a given entry state.
+struct BlockVisit {
+    /// State after the statements of the block, right before its terminator.
+    exit: State,
+    /// Feasible successors along with the state on entry to each of them.
+    successors: Vec<(smir::mir::BasicBlockIdx, State)>,
+    /// Errors encountered while interpreting the block.
+    errors: Vec<Error>,
+    /// Alarm raised by the assertion terminating the block, if any.
+    alarm: Option<Alarm>,
+}
+
+fn visit_block(bb: smir::mir::BasicBlockIdx, body: &smir::mir::Body, entry: &State, summaries: &mut Summaries) -> BlockVisit {
+    let block = &body.blocks[bb];
+    let mut state = entry.clone();
+    let mut conditions = ConditionTracker::new();
+    let mut errors = interpret_block(block, body, &mut state, &mut conditions);
+    let alarm = check_assert(&block.terminator, &state);
+
+    let successors = match interpret_terminator(&block.terminator, body, &state, &conditions, summaries) {
+        Ok(successors) => successors,
+        Err(e) => {
+            // Conservatively assume control can reach every successor without any changes to
+            // the state.
+            errors.push(e);
+            get_successors(&block.terminator).into_iter().map(|succ| (succ, state.clone())).collect()
+        }
+    };
+    BlockVisit { exit: state, successors, errors, alarm }
 }
 
-fn interpret_block(block: &smir::mir::BasicBlock, state: &mut HashMap<smir::mir::Local, AbstractValue>) -> Result<(), Error> {
+/// Stores the states flowing along the edges out of block `bb`, joining the states of edges to the
+/// same successor (e.g. several switch values with the same target).
+fn record_edges(bb: smir::mir::BasicBlockIdx, successors: Vec<(smir::mir::BasicBlockIdx, State)>, edges: &mut HashMap<(smir::mir::BasicBlockIdx, smir::mir::BasicBlockIdx), State>) {
+    for (succ, state) in successors {
+        let joined = match edges.remove(&(bb, succ)) {
+            Some(existing) => join_states(&existing, &state),
+            None => state,
+        };
+        edges.insert((bb, succ), joined);
+    }
+}
+
+/// Returns true if every local of state `a` is also in state `b` with a value that is at least as
+/// large.
+fn state_leq(a: &State, b: &State) -> bool {
+    a.iter().all(|(local, value)| match b.get(local) {
+        Some(other) => value.leq(other),
+        None => value.is_bottom(),
+    })
+}
+
+/// Joins two states local by local. A local that is only present in one of the states hasn't been
+/// initialized along the other path, so the value from the initialized path is kept.
+fn join_states(a: &State, b: &State) -> State {
+    let mut joined = a.clone();
+    for (local, value) in b {
+        let new_value = match a.get(local) {
+            Some(existing) => existing.join(value),
+            None => value.clone(),
+        };
+        joined.insert(*local, new_value);
+    }
+    joined
+}
+
+/// Widens state `a` with state `b` local by local.
+fn widen_states(a: &State, b: &State) -> State {
+    let mut widened = a.clone();
+    for (local, value) in b {
+        let new_value = match a.get(local) {
+            Some(existing) => existing.widen(value),
+            None => value.clone(),
+        };
+        widened.insert(*local, new_value);
+    }
+    widened
+}
+
+/// Narrows state `a` with state `b` local by local.
+fn narrow_states(a: &State, b: &State) -> State {
+    let mut narrowed = a.clone();
+    for (local, value) in b {
+        let new_value = match a.get(local) {
+            Some(existing) => existing.narrow(value),
+            None => value.clone(),
+        };
+        narrowed.insert(*local, new_value);
+    }
+    narrowed
+}
+
+/// Interprets the statements of a block, updating the state in place. Interpretation continues
+/// past statements that can't be interpreted; their destination is reset to top instead so the
+/// resulting state still over-approximates the concrete one. Returns the errors encountered.
+fn interpret_block(block: &smir::mir::BasicBlock, body: &smir::mir::Body, state: &mut State, conditions: &mut ConditionTracker) -> Vec<Error> {
+    let mut errors = Vec::new();
     for statement in &block.statements {
-        interpret_statement(statement, state)?;
+        if let Err(e) = interpret_statement(statement, body, state) {
+            if let smir::mir::StatementKind::Assign(place, _) = &statement.kind {
+                havoc_place(place, body, state);
+            }
+            errors.push(e);
+        }
+        match &statement.kind {
+            smir::mir::StatementKind::Assign(place, rvalue) => conditions.assign(place, rvalue),
+            smir::mir::StatementKind::Deinit(place) => conditions.invalidate(place.local),
+            _ => (),
+        }
     }
-    Ok(())
+    errors
 }
 
-fn interpret_statement(statement: &smir::mir::Statement, state: &mut HashMap<smir::mir::Local, AbstractValue>) -> Result<(), Error> {
+/// Sets the local of the place to the top value of its type. Writes through a dereference may
+/// modify any mutably borrowed local, so those are reset as well.
+fn havoc_place(place: &smir::mir::Place, body: &smir::mir::Body, state: &mut State) {
+    havoc_local(place.local, body, state);
+    if place.projection.iter().any(|proj| matches!(proj, smir::mir::ProjectionElem::Deref)) {
+        for local in mutably_borrowed_locals(body) {
+            havoc_local(local, body, state);
+        }
+    }
+}
+
+/// Sets the local to the top value of its type, or removes it from the state if its type can't be
+/// abstracted.
+fn havoc_local(local: smir::mir::Local, body: &smir::mir::Body, state: &mut State) {
+    match AbstractValue::new(&body.locals()[local].ty) {
+        Ok(top) => state.insert(local, top),
+        Err(_) => state.remove(&local),
+    };
+}
+
+/// Interprets a block terminator. Returns each successor block that control may flow to along
+/// with the state on entry to that successor. Successors that can't be reached given the current
+/// state are omitted.
+fn interpret_terminator(terminator: &smir::mir::Terminator, body: &smir::mir::Body, state: &State, conditions: &ConditionTracker, summaries: &mut Summaries) -> Result<Vec<(smir::mir::BasicBlockIdx, State)>, Error> {
+    match &terminator.kind {
+        smir::mir::TerminatorKind::Goto { target } => Ok(vec![(*target, state.clone())]),
+        smir::mir::TerminatorKind::SwitchInt { discr, targets } => {
+            let discr_val = interpret_operand(discr, state)?;
+            let branch_values: Vec<u128> = targets.branches().map(|(value, _)| value).collect();
+            let mut successors = Vec::new();
+            for (value, target) in targets.branches() {
+                if !switch_value_feasible(&discr_val, value) {
+                    continue;
+                }
+                let succ_state = match &discr_val {
+                    AbstractValue::Bool(_) => refine_bool_operand(discr, value != 0, state, conditions),
+                    _ => refine_int_operand(discr, &discr_val, value, Comparison::Eq, state.clone(), conditions),
+                };
+                successors.extend(succ_state.map(|s| (target, s)));
+            }
+
+            // The otherwise branch is infeasible if the discriminant is known to match one of the
+            // explicit branch values, or if it is bottom.
+            let matches_branch = branch_values.iter()
+                .any(|value| switch_value_certain(&discr_val, *value));
+            if !matches_branch && !discr_val.is_bottom() {
+                // For a boolean discriminant, the otherwise branch is taken for whichever of
+                // false (0) or true (1) isn't an explicit branch value.
+                let outcome = match (&discr_val, branch_values.contains(&0), branch_values.contains(&1)) {
+                    (AbstractValue::Bool(_), true, false) => Some(true),
+                    (AbstractValue::Bool(_), false, true) => Some(false),
+                    _ => None,
+                };
+                let succ_state = match outcome {
+                    Some(outcome) => refine_bool_operand(discr, outcome, state, conditions),
+                    // An integer discriminant differs from every explicit branch value. Only values
+                    // at the bounds of an interval can be excluded, so they are excluded from both
+                    // ends in turn, e.g. 0, 1 and 2 from [0, 10].
+                    None => {
+                        let mut sorted = branch_values.clone();
+                        sorted.sort_unstable();
+                        sorted.iter().chain(sorted.iter().rev()).try_fold(state.clone(), |refined, value| {
+                            let Ok(discr_val) = interpret_operand(discr, &refined) else { return Some(refined) };
+                            refine_int_operand(discr, &discr_val, *value, Comparison::Ne, refined, conditions)
+                        })
+                    }
+                };
+                successors.extend(succ_state.map(|s| (targets.otherwise(), s)));
+            }
+            Ok(successors)
+        }
+        // The return value is read from the exit state of the block once the fixpoint is reached.
+        smir::mir::TerminatorKind::Return => Ok(vec![]),
+        smir::mir::TerminatorKind::Unreachable => Ok(vec![]),
+        smir::mir::TerminatorKind::Assert { cond, expected, target, .. } => {
+            // The assertion panics unless the condition matches the expected value. We only follow
+            // the non-panicking edge, which is infeasible if the condition never matches or is
+            // bottom.
+            match interpret_operand(cond, state)? {
+                AbstractValue::Bool(b) if matches!(b.equals(&AbstractBool::from(*expected)), AbstractBool::False | AbstractBool::Bot) => Ok(vec![]),
+                _ => Ok(refine_bool_operand(cond, *expected, state, conditions)
+                    .map(|s| vec![(*target, s)])
+                    .unwrap_or_default()),
+            }
+        }
+        // Dropping a value doesn't change any of the abstract values we track.
+        smir::mir::TerminatorKind::Drop { target, .. } => Ok(vec![(*target, state.clone())]),
+        smir::mir::TerminatorKind::Call { func, args, destination, target, .. } => {
+            let target = match target {
+                Some(target) => target,
+                // Diverging call
+                None => return Ok(vec![]),
+            };
+
+            let mut succ_state = state.clone();
+            // The callee may write through any mutable reference it was given
+            for local in mutably_borrowed_locals(body) {
+                havoc_local(local, body, &mut succ_state);
+            }
+            // Calls to functions without a summary (e.g. functions from other crates, or
+            // recursive calls to a function that is still being analyzed) are treated as opaque,
+            // so the destination could hold any value of its type.
+            match apply_summary(func, args, state, summaries) {
+                Some(ret) if ret.is_bottom() => return Ok(vec![]),
+                Some(ret) => {
+                    if set_place_value(destination, ret, body, &mut succ_state).is_err() {
+                        havoc_place(destination, body, &mut succ_state);
+                    }
+                }
+                None => havoc_place(destination, body, &mut succ_state),
+            }
+            Ok(vec![(*target, succ_state)])
+        }
+        // Only non-panicking paths are analyzed, so unwinding terminators have no successors.
+        smir::mir::TerminatorKind::Resume | smir::mir::TerminatorKind::Abort => Ok(vec![]),
+        _ => Err(Error::with_message(
+            ErrorKind::NotImplementedError,
+            format!("Terminator not supported: {:?}", terminator.kind),
+        )),
+    }
+}
+
+/// Checks whether the assertion terminating a block can fail in the given state. Returns an alarm
+/// if it can, and None if the check is proven to succeed (or isn't one we report).
+fn check_assert(terminator: &smir::mir::Terminator, state: &State) -> Option<Alarm> {
+    let (cond, expected, msg) = match &terminator.kind {
+        smir::mir::TerminatorKind::Assert { cond, expected, msg, .. } => (cond, expected, msg),
+        _ => return None,
+    };
+    let kind = match msg {
+        smir::mir::AssertMessage::Overflow(op, _, _) => AlarmKind::Overflow(*op),
+        smir::mir::AssertMessage::OverflowNeg(_) => AlarmKind::OverflowNeg,
+        smir::mir::AssertMessage::DivisionByZero(_) => AlarmKind::DivisionByZero,
+        smir::mir::AssertMessage::RemainderByZero(_) => AlarmKind::RemainderByZero,
+        smir::mir::AssertMessage::BoundsCheck { .. } => AlarmKind::BoundsCheck,
+        _ => return None,
+    };
+
+    // The assertion fails when the condition doesn't have the expected value
+    let fails = match interpret_operand(cond, state) {
+        Ok(AbstractValue::Bool(b)) => b.equals(&AbstractBool::from(*expected)).not(),
+        // A condition that can't be interpreted could have either value
+        _ => AbstractBool::Top,
+    };
+    Verdict::from_failure(fails).map(|verdict| Alarm { kind, verdict, span: terminator.span })
+}
+
+/// Refines the state assuming that the boolean operand evaluated to `outcome`. If the operand holds
+/// the result of a tracked comparison, the compared values are refined as well. Returns None if
+/// the operand can't evaluate to `outcome` in the given state.
+fn refine_bool_operand(op: &smir::mir::Operand, outcome: bool, state: &State, conditions: &ConditionTracker) -> Option<State> {
+    let place = match op {
+        smir::mir::Operand::Copy(place) | smir::mir::Operand::Move(place) if place.projection.is_empty() => place,
+        _ => return Some(state.clone()),
+    };
+
+    let mut refined = state.clone();
+    refined.insert(place.local, AbstractValue::Bool(AbstractBool::from(outcome)));
+    if let Some(condition) = conditions.condition(place.local) {
+        refine_condition(condition, outcome, &mut refined, conditions)?;
+    }
+    Some(refined)
+}
+
+/// Refines the integer operand of a switch in the state, assuming it compares to the raw bits
+/// `value` as given by `comparison` (either `Eq` or `Ne`). Returns None if it can't.
+fn refine_int_operand(op: &smir::mir::Operand, discr: &AbstractValue, value: u128, comparison: Comparison, mut state: State, conditions: &ConditionTracker) -> Option<State> {
+    let value = match discr {
+        AbstractValue::UintInterval(ty, _) => AbstractValue::UintInterval(*ty, Interval::from(value)),
+        AbstractValue::IntInterval(ty, _) => AbstractValue::IntInterval(*ty, Interval::from(int_from_bits(*ty, value))),
+        _ => return Some(state),
+    };
+    let refined = refine_value(discr, comparison, &value)?;
+    assign_refined(op, refined, &mut state, conditions);
+    Some(state)
+}
+
+/// Refines the operands of the condition in the state assuming the condition evaluated to
+/// `outcome`. Returns None if the condition can't evaluate to `outcome`.
+fn refine_condition(condition: &Condition, outcome: bool, state: &mut State, conditions: &ConditionTracker) -> Option<()> {
+    let comparison = if outcome { condition.comparison } else { condition.comparison.negate() };
+    let (left, right) = match (interpret_operand(&condition.left, state), interpret_operand(&condition.right, state)) {
+        (Ok(left), Ok(right)) => (left, right),
+        // Nothing can be learned about operands that can't be interpreted
+        _ => return Some(()),
+    };
+
+    let refined_left = refine_value(&left, comparison, &right)?;
+    let refined_right = refine_value(&right, comparison.flip(), &left)?;
+    assign_refined(&condition.left, refined_left, state, conditions);
+    assign_refined(&condition.right, refined_right, state, conditions);
+    Some(())
+}
+
+/// Refines `value` to the elements for which `value <comparison> other` can hold. Returns None if
+/// there are no such elements.
+fn refine_value(value: &AbstractValue, comparison: Comparison, other: &AbstractValue) -> Option<AbstractValue> {
+    if value.is_bottom() || other.is_bottom() {
+        return None;
+    }
+    match (value, other) {
+        (AbstractValue::IntInterval(ty, v), AbstractValue::IntInterval(_, o)) => {
+            refine_interval(v, comparison, o).map(|i| AbstractValue::IntInterval(*ty, i))
+        }
+        (AbstractValue::UintInterval(ty, v), AbstractValue::UintInterval(_, o)) => {
+            refine_interval(v, comparison, o).map(|i| AbstractValue::UintInterval(*ty, i))
+        }
+        (AbstractValue::Bool(v), AbstractValue::Bool(o)) => {
+            // Only equality comparisons between booleans can be refined, and only against a
+            // boolean with a known value.
+            let expected = match (comparison, o) {
+                (Comparison::Eq, AbstractBool::True | AbstractBool::False) => *o,
+                (Comparison::Ne, AbstractBool::True | AbstractBool::False) => o.not(),
+                _ => return Some(value.clone()),
+            };
+            match v {
+                AbstractBool::Top => Some(AbstractValue::Bool(expected)),
+                v if *v == expected => Some(value.clone()),
+                _ => None,
+            }
+        }
+        _ => Some(value.clone()),
+    }
+}
+
+fn refine_interval<T: Integer>(value: &Interval<T>, comparison: Comparison, other: &Interval<T>) -> Option<Interval<T>> {
+    match comparison {
+        Comparison::Lt => value.refine_less_than(other),
+        Comparison::Le => value.refine_less_equal(other),
+        Comparison::Gt => value.refine_greater_than(other),
+        Comparison::Ge => value.refine_greater_equal(other),
+        Comparison::Eq => value.refine_equal(other),
+        Comparison::Ne => value.refine_not_equal(other),
+    }
+}
+
+/// Writes a refined value back to the local the operand reads from, as well as to the local that
+/// it is a copy of (if any).
+fn assign_refined(op: &smir::mir::Operand, value: AbstractValue, state: &mut State, conditions: &ConditionTracker) {
+    if let smir::mir::Operand::Copy(place) | smir::mir::Operand::Move(place) = op {
+        if place.projection.is_empty() {
+            if let Some(source) = conditions.copy_source(place.local) {
+                state.insert(source, value.clone());
+            }
+            state.insert(place.local, value);
+        }
+    }
+}
+
+/// Instantiates the summary of the called function with the abstract arguments of the call, giving
+/// the abstract value it returns. The value is bottom if the call can never return, e.g. because
+/// one of its arguments is bottom. Returns None if there is no summary for the callee.
+fn apply_summary(func: &smir::mir::Operand, args: &[smir::mir::Operand], state: &State, summaries: &mut Summaries) -> Option<AbstractValue> {
+    let name = get_callee_name(func)?;
+    let summary = summaries.summaries.get(&name)?;
+    if args.len() != summary.arguments.len() {
+        return None;
+    }
+    // Arguments that can't be interpreted could hold any value the function accepts.
+    let arg_values: Vec<AbstractValue> = args.iter()
+        .zip(&summary.arguments)
+        .map(|(arg, general)| interpret_operand(arg, state).unwrap_or_else(|_| general.clone()))
+        .collect();
+    summaries.call(&name, &arg_values)
+}
+
+/// Abstract value returned by an analyzed body: the value of `_0` at the reachable `Return`
+/// terminators, or bottom if the body never returns. Falls back to `default` if the return value
+/// couldn't be determined.
+fn return_value(analysis: &BodyAnalysis, default: &AbstractValue) -> AbstractValue {
+    match &analysis.return_state {
+        Some(state) => state.get(&0).cloned().unwrap_or_else(|| default.clone()),
+        None => default.bottom(),
+    }
+}
+
+/// Returns true if a switch on the given discriminant may take the branch for `value`. No branch
+/// can be taken on a bottom discriminant.
+fn switch_value_feasible(discr: &AbstractValue, value: u128) -> bool {
+    let equals = match discr {
+        AbstractValue::Bool(b) => b.equals(&AbstractBool::from(value != 0)),
+        AbstractValue::UintInterval(_, i) => i.equals(&Interval::from(value)),
+        // Switch values are the raw bits of the discriminant, so signed values need to be
+        // sign-extended based on the width of their type.
+        AbstractValue::IntInterval(ty, i) => i.equals(&Interval::from(int_from_bits(*ty, value))),
+        _ => return !discr.is_bottom(),
+    };
+    matches!(equals, AbstractBool::True | AbstractBool::Top)
+}
+
+/// Returns true if a switch on the given discriminant always takes the branch for `value`.
+fn switch_value_certain(discr: &AbstractValue, value: u128) -> bool {
+    match discr {
+        AbstractValue::Bool(b) => b.equals(&AbstractBool::from(value != 0)) == AbstractBool::True,
+        AbstractValue::UintInterval(_, i) => i.equals(&Interval::from(value)) == AbstractBool::True,
+        AbstractValue::IntInterval(ty, i) => i.equals(&Interval::from(int_from_bits(*ty, value))) == AbstractBool::True,
+        _ => false,
+    }
+}
+
+fn interpret_statement(statement: &smir::mir::Statement, body: &smir::mir::Body, state: &mut State) -> Result<(), Error> {
     match &statement.kind {
         smir::mir::StatementKind::Assign(place, rvalue) => {
             let val = interpret_rvalue(&rvalue, state)?;
-            state.insert(place.local, val);
-            Ok(())
-        }
-        smir::mir::StatementKind::Deinit(place) => {
-            state.insert(place.local, AbstractValue::Uninit);
-            Ok(())
+            set_place_value(place, val, body, state)
         }
+        smir::mir::StatementKind::Deinit(place) => set_place_value(place, AbstractValue::Uninit, body, state),
+        // Storage markers don't affect the abstract values of locals.
+        smir::mir::StatementKind::StorageLive(_)
+        | smir::mir::StatementKind::StorageDead(_)
+        | smir::mir::StatementKind::Nop => Ok(()),
         _ => Err(Error::new(ErrorKind::NotImplementedError)),
     }
 }
 
-fn interpret_rvalue(rvalue: &smir::mir::Rvalue, state: &mut HashMap<smir::mir::Local, AbstractValue>) -> Result<AbstractValue, Error> {
+fn interpret_rvalue(rvalue: &smir::mir::Rvalue, state: &State) -> Result<AbstractValue, Error> {
     match rvalue {
         smir::mir::Rvalue::Use(op) => interpret_operand(op, state),
-        // TODO(klinvill): currently we assume checked operations never fail
         smir::mir::Rvalue::BinaryOp(op, left, right) => interpret_binop(op, left, right, state),
+        smir::mir::Rvalue::UnaryOp(op, operand) => interpret_unop(op, operand, state),
         smir::mir::Rvalue::CheckedBinaryOp(op, left, right) => {
-            let v = interpret_binop(op, left, right, state)?;
+            let left_val = interpret_operand(left, state)?;
+            let right_val = interpret_operand(right, state)?;
+            // The result is only used if the overflow check passes, so it only needs to cover the
+            // results that don't overflow.
+            let (v, overflow) = left_val.arithmetic(op, &right_val, OverflowMode::Panic)?;
             // Checked operations return the value and a boolean flag that checks if an operation succeeded
-            Ok(AbstractValue::Tuple(vec![v, AbstractValue::Bool(booleans::AbstractBool::False)]))
+            Ok(AbstractValue::Tuple(vec![v, AbstractValue::Bool(overflow)]))
+        }
+        smir::mir::Rvalue::Ref(_, _, place) => {
+            let pointee = get_place_value(place, state)?
+                .ok_or(Error::new(ErrorKind::InterpreterError))?;
+            Ok(AbstractValue::Ref(Box::new(pointee)))
+        }
+        smir::mir::Rvalue::Aggregate(smir::mir::AggregateKind::Tuple, operands) => {
+            let values: Result<Vec<_>, _> = operands.iter().map(|op| interpret_operand(op, state)).collect();
+            values.map(AbstractValue::Tuple)
+        }
+        smir::mir::Rvalue::Aggregate(smir::mir::AggregateKind::Array(elem_ty), operands) => {
+            let mut elem = match operands.first() {
+                Some(op) => interpret_operand(op, state)?,
+                None => AbstractValue::new(elem_ty)?,
+            };
+            for op in operands.iter().skip(1) {
+                elem = elem.join(&interpret_operand(op, state)?);
+            }
+            Ok(AbstractValue::Array(Box::new(elem), Interval::from(operands.len() as u128)))
+        }
+        smir::mir::Rvalue::Repeat(op, count) => {
+            Ok(AbstractValue::Array(Box::new(interpret_operand(op, state)?), array_length(count)))
+        }
+        smir::mir::Rvalue::Len(place) => {
+            let len = match get_place_value(place, state)? {
+                Some(AbstractValue::Array(_, len)) => len,
+                _ => length_bounds(),
+            };
+            Ok(AbstractValue::UintInterval(smir::ty::UintTy::Usize, len))
+        }
+        // Unsizing a reference to an array into a reference to a slice keeps its length
+        smir::mir::Rvalue::Cast(smir::mir::CastKind::PointerCoercion(smir::mir::PointerCoercion::Unsize), op, _) => {
+            match interpret_operand(op, state)? {
+                value @ AbstractValue::Ref(_) => Ok(value),
+                _ => Err(Error::new(ErrorKind::NotImplementedError)),
+            }
         }
         _ => Err(Error::new(ErrorKind::NotImplementedError)),
     }
 }
 
-fn interpret_binop(binop: &smir::mir::BinOp, left: &smir::mir::Operand, right: &smir::mir::Operand, state: &mut HashMap<smir::mir::Local, AbstractValue>) -> Result<AbstractValue, Error> {
+fn interpret_binop(binop: &smir::mir::BinOp, left: &smir::mir::Operand, right: &smir::mir::Operand, state: &State) -> Result<AbstractValue, Error> {
     let left_val = interpret_operand(left, state)?;
     let right_val = interpret_operand(right, state)?;
     match binop {
-        smir::mir::BinOp::Add => {
-            match (left_val, right_val) {
-                (AbstractValue::IntInterval(l), AbstractValue::IntInterval(r)) => Ok(AbstractValue::IntInterval(l + r)),
-                (AbstractValue::UintInterval(l), AbstractValue::UintInterval(r)) => Ok(AbstractValue::UintInterval(l + r)),
-                _ => Err(Error::new(ErrorKind::NotImplementedError)),
-            }
+        smir::mir::BinOp::Add | smir::mir::BinOp::Sub | smir::mir::BinOp::Mul | smir::mir::BinOp::Div | smir::mir::BinOp::Rem => {
+            // Unchecked arithmetic wraps on overflow
+            let (v, _) = left_val.arithmetic(binop, &right_val, OverflowMode::Wrap)?;
+            Ok(v)
         }
-        smir::mir::BinOp::Eq => {
-            match (left_val, right_val) {
-                (AbstractValue::Bool(l), AbstractValue::Bool(r)) => Ok(AbstractValue::Bool(l.equals(&r))),
-                (AbstractValue::IntInterval(l), AbstractValue::IntInterval(r)) => Ok(AbstractValue::Bool(l.equals(&r))),
-                (AbstractValue::UintInterval(l), AbstractValue::UintInterval(r)) => Ok(AbstractValue::Bool(l.equals(&r))),
-                _ => Err(Error::new(ErrorKind::NotImplementedError)),
+        smir::mir::BinOp::Eq | smir::mir::BinOp::Ne => {
+            let equals = match (left_val, right_val) {
+                (AbstractValue::Bool(l), AbstractValue::Bool(r)) => l.equals(&r),
+                (AbstractValue::IntInterval(_, l), AbstractValue::IntInterval(_, r)) => l.equals(&r),
+                (AbstractValue::UintInterval(_, l), AbstractValue::UintInterval(_, r)) => l.equals(&r),
+                _ => return Err(Error::new(ErrorKind::NotImplementedError)),
+            };
+            match binop {
+                smir::mir::BinOp::Eq => Ok(AbstractValue::Bool(equals)),
+                _ => Ok(AbstractValue::Bool(equals.not())),
             }
         }
-        smir::mir::BinOp::Lt => {
+        // Bitwise operations on booleans, e.g. the conjunction of the two comparisons that check signed
+        // division for `MIN / -1`
+        smir::mir::BinOp::BitAnd | smir::mir::BinOp::BitOr | smir::mir::BinOp::BitXor => {
             match (left_val, right_val) {
-                (AbstractValue::IntInterval(l), AbstractValue::IntInterval(r)) => Ok(AbstractValue::Bool(l.less_than(&r))),
-                (AbstractValue::UintInterval(l), AbstractValue::UintInterval(r)) => Ok(AbstractValue::Bool(l.less_than(&r))),
+                (AbstractValue::Bool(l), AbstractValue::Bool(r)) => Ok(AbstractValue::Bool(match binop {
+                    smir::mir::BinOp::BitAnd => l.and(&r),
+                    smir::mir::BinOp::BitOr => l.or(&r),
+                    _ => l.xor(&r),
+                })),
                 _ => Err(Error::new(ErrorKind::NotImplementedError)),
             }
         }
+        smir::mir::BinOp::Lt | smir::mir::BinOp::Le | smir::mir::BinOp::Gt | smir::mir::BinOp::Ge => {
+            // Every ordering can be expressed using less than: a <= b is !(b < a), a > b is b < a,
+            // and a >= b is !(a < b).
+            let (l, r, negate) = match binop {
+                smir::mir::BinOp::Lt => (left_val, right_val, false),
+                smir::mir::BinOp::Le => (right_val, left_val, true),
+                smir::mir::BinOp::Gt => (right_val, left_val, false),
+                _ => (left_val, right_val, true),
+            };
+            let less_than = match (l, r) {
+                (AbstractValue::IntInterval(_, l), AbstractValue::IntInterval(_, r)) => l.less_than(&r),
+                (AbstractValue::UintInterval(_, l), AbstractValue::UintInterval(_, r)) => l.less_than(&r),
+                _ => return Err(Error::new(ErrorKind::NotImplementedError)),
+            };
+            Ok(AbstractValue::Bool(if negate { less_than.not() } else { less_than }))
+        }
+        _ => Err(Error::new(ErrorKind::NotImplementedError)),
+    }
+}
+
+fn interpret_unop(unop: &smir::mir::UnOp, operand: &smir::mir::Operand, state: &State) -> Result<AbstractValue, Error> {
+    match (unop, interpret_operand(operand, state)?) {
+        (smir::mir::UnOp::Neg, value) => Ok(value.negate(OverflowMode::Wrap)?.0),
+        (smir::mir::UnOp::Not, AbstractValue::Bool(b)) => Ok(AbstractValue::Bool(b.not())),
         _ => Err(Error::new(ErrorKind::NotImplementedError)),
     }
 }
 
-fn interpret_operand(op: &smir::mir::Operand, state: &mut HashMap<smir::mir::Local, AbstractValue>) -> Result<AbstractValue, Error> {
+fn interpret_operand(op: &smir::mir::Operand, state: &State) -> Result<AbstractValue, Error> {
     match op {
         smir::mir::Operand::Copy(place) | smir::mir::Operand::Move(place) => {
-            let value = get_place_value(&place, &state)?
+            let value = get_place_value(&place, state)?
                 .ok_or(Error::new(ErrorKind::InterpreterError))?;
             // TODO(klinvill): Clone could be expensive. Should we instead wrap the abstract value
             //  in a shared reference like Rc?
             Ok(value.clone())
         }
-        smir::mir::Operand::Constant(c) => match c.literal.ty().kind() {
-            TyKind::RigidTy(RigidTy::Bool) => Ok(AbstractValue::Bool(booleans::AbstractBool::from(&c.literal))),
-            // rustc_middle::mir::interpret::ConstValue::Scalar(s) => match s {
-            //     rustc_middle::mir::interpret::Scalar::Int(i) => {
-            //         let bits = i.to_bits(i.size()).unwrap();
-            //         match ty.kind() {
-            //             rustc_middle::ty::TyKind::Bool => {
-            //                 if bits == 0 {
-            //                     Ok(AbstractValue::Bool(booleans::AbstractBool::False))
-            //                 } else {
-            //                     Ok(AbstractValue::Bool(booleans::AbstractBool::True))
-            //                 }
-            //             }
-            //             rustc_middle::ty::TyKind::Int(_) => {
-            //                 let num = match i.size().bytes_usize() {
-            //                     1 => i128::from(bits as i8),
-            //                     2 => i128::from(bits as i16),
-            //                     4 => i128::from(bits as i32),
-            //                     8 => i128::from(bits as i64),
-            //                     16 => i128::from(bits as i128),
-            //                     _ => Err(Error::new(ErrorKind::NotImplementedError))?,
-            //                 };
-            //                 Ok(AbstractValue::IntInterval(
-            //                     interval::Interval::from(num)
-            //                 ))
-            //             },
-            //             rustc_middle::ty::TyKind::Uint(_) => {
-            //                 let num = match i.size().bytes_usize() {
-            //                     1 => u128::from(bits as u8),
-            //                     2 => u128::from(bits as u16),
-            //                     4 => u128::from(bits as u32),
-            //                     8 => u128::from(bits as u64),
-            //                     16 => u128::from(bits as u128),
-            //                     _ => Err(Error::new(ErrorKind::NotImplementedError))?,
-            //                 };
-            //                 Ok(AbstractValue::UintInterval(
-            //                     interval::Interval::from(num)
-            //                 ))
-            //             },
-            //             _ => Err(Error::new(ErrorKind::NotImplementedError)),
-            //         }
-            //     },
-            //     _ => Err(Error::new(ErrorKind::NotImplementedError)),
-            // },
-            //     _ => Err(Error::new(ErrorKind::NotImplementedError)),
-            // },
-            _ => Err(Error::new(ErrorKind::NotImplementedError)),
-        }
-    }
-}
-
-
-fn get_place_value(place: &smir::mir::Place, state: &HashMap<smir::mir::Local, AbstractValue>) -> Result<Option<AbstractValue>, Error> {
-    Ok(state.get(&place.local).cloned())
-}
-
-// fn follow_projection<'a, V: std::fmt::Debug, T: std::fmt::Debug>(val: &'a AbstractValue, proj: &smir::mir::ProjectionElem<V,T>) -> Result<Option<&'a AbstractValue>, Error> {
-//     match proj {
-//         smir::mir::ProjectionElem::Field(f, ty) => {
-//             Ok(val.get(f.index()))
-//         },
-//         _ => Err(Error::with_message(
-//             ErrorKind::NotImplementedError,
-//             format!("Projection handling is not implemented for projection {:?}", proj),
-//         )),
-//     }
-// }
+        smir::mir::Operand::Constant(c) => AbstractValue::from_const(&c.literal),
+    }
+}
+
+
+fn get_place_value(place: &smir::mir::Place, state: &State) -> Result<Option<AbstractValue>, Error> {
+    let mut value = match state.get(&place.local) {
+        Some(value) => value.clone(),
+        None => return Ok(None),
+    };
+    for proj in &place.projection {
+        value = follow_projection(&value, proj)?;
+    }
+    Ok(Some(value))
+}
+
+/// Returns the abstract value of the part of `val` that the projection selects.
+fn follow_projection(val: &AbstractValue, proj: &smir::mir::ProjectionElem) -> Result<AbstractValue, Error> {
+    match (proj, val) {
+        (smir::mir::ProjectionElem::Field(f, _), AbstractValue::Tuple(_)) => {
+            val.get(*f).cloned().ok_or(Error::with_message(
+                ErrorKind::InterpreterError,
+                "Tried to index entry outside tuple limits".to_string(),
+            ))
+        }
+        // Fields of values that aren't modeled (e.g. structs) could hold any value of their type
+        (smir::mir::ProjectionElem::Field(_, ty), _) => AbstractValue::new(ty),
+        (smir::mir::ProjectionElem::Deref, AbstractValue::Ref(pointee)) => Ok(pointee.as_ref().clone()),
+        (smir::mir::ProjectionElem::Index(_) | smir::mir::ProjectionElem::ConstantIndex { .. }, AbstractValue::Array(elem, _)) => {
+            Ok(elem.as_ref().clone())
+        }
+        // A subslice has the same elements as the array it is taken from. Only subslices that
+        // don't count from the end have a length that is known without the array's length.
+        (smir::mir::ProjectionElem::Subslice { from, to, from_end }, AbstractValue::Array(elem, len)) => {
+            let sub_len = if *from_end {
+                Interval { lower: IntervalElem::Elem(0), upper: len.upper }
+            } else {
+                Interval::from(to.saturating_sub(*from) as u128)
+            };
+            Ok(AbstractValue::Array(elem.clone(), sub_len))
+        }
+        // Enum variants aren't distinguished, so downcasting doesn't change the value.
+        (smir::mir::ProjectionElem::Downcast(_), _)
+        | (smir::mir::ProjectionElem::OpaqueCast(_), _)
+        | (smir::mir::ProjectionElem::Subtype(_), _) => Ok(val.clone()),
+        _ => Err(Error::with_message(
+            ErrorKind::NotImplementedError,
+            format!("Projection handling is not implemented for projection {:?} of {:?}", proj, val),
+        )),
+    }
+}
+
+/// Assigns a value to a place. Writes through a dereference may modify any mutably borrowed local,
+/// so those are reset to top.
+fn set_place_value(place: &smir::mir::Place, value: AbstractValue, body: &smir::mir::Body, state: &mut State) -> Result<(), Error> {
+    if place.projection.is_empty() {
+        state.insert(place.local, value);
+        return Ok(());
+    }
+
+    let target = state.get_mut(&place.local).ok_or(Error::with_message(
+        ErrorKind::InterpreterError,
+        format!("Tried to assign to a projection of uninitialized local {}", place.local),
+    ))?;
+    update_projected(target, &place.projection, value, false)?;
+
+    if place.projection.iter().any(|proj| matches!(proj, smir::mir::ProjectionElem::Deref)) {
+        for local in mutably_borrowed_locals(body) {
+            if local != place.local {
+                havoc_local(local, body, state);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes a value to the part of `target` that the projections select. A weak update joins the
+/// value with the existing one instead of replacing it, which is needed when writing to one of the
+/// elements summarized by an array value.
+fn update_projected(target: &mut AbstractValue, projection: &[smir::mir::ProjectionElem], value: AbstractValue, weak: bool) -> Result<(), Error> {
+    let (proj, rest) = match projection.split_first() {
+        Some(split) => split,
+        None => {
+            *target = if weak { target.join(&value) } else { value };
+            return Ok(());
+        }
+    };
+
+    match proj {
+        smir::mir::ProjectionElem::Field(..) if matches!(target, AbstractValue::Uninit) => {
+            // Uninit is bottom, so writing a single field can't leave it as is. The error makes the
+            // caller reset the value to the top of its type, which the fields are then written to.
+            Err(Error::with_message(
+                ErrorKind::NotImplementedError,
+                "Cannot write a field of an uninitialized value".to_string(),
+            ))
+        }
+        smir::mir::ProjectionElem::Field(f, _) => {
+            if !matches!(target, AbstractValue::Tuple(_)) {
+                // Fields of values that aren't modeled always read as top, so there is nothing to
+                // update.
+                return Ok(());
+            }
+            let entry = target.get_mut(*f).ok_or(Error::with_message(
+                ErrorKind::InterpreterError,
+                "Tried to index entry outside tuple limits".to_string(),
+            ))?;
+            update_projected(entry, rest, value, weak)
+        }
+        smir::mir::ProjectionElem::Deref => match target {
+            AbstractValue::Ref(pointee) => update_projected(pointee.as_mut(), rest, value, weak),
+            _ => Err(Error::with_message(
+                ErrorKind::NotImplementedError,
+                format!("Cannot write through a dereference of {:?}", target),
+            )),
+        },
+        smir::mir::ProjectionElem::Index(_) | smir::mir::ProjectionElem::ConstantIndex { .. } => match target {
+            AbstractValue::Array(elem, _) => update_projected(elem.as_mut(), rest, value, true),
+            _ => Err(Error::with_message(
+                ErrorKind::NotImplementedError,
+                format!("Cannot index into {:?}", target),
+            )),
+        },
+        smir::mir::ProjectionElem::Subslice { .. }
+        | smir::mir::ProjectionElem::Downcast(_)
+        | smir::mir::ProjectionElem::OpaqueCast(_)
+        | smir::mir::ProjectionElem::Subtype(_) => update_projected(target, rest, value, weak),
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+mod soundness;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use smir::ty::{IntTy, UintTy};
+
+    extern crate rustc_middle;
+    use rustc_middle::ty::TyCtxt;
+
+    #[test]
+    fn fixpoint_joins_states_at_loop_head() {
+        let body = r"fn spin(b: bool) -> bool { let mut c = b; while c { c = false; } c }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let analysis = interpret_body(&body, &vec![AbstractValue::Bool(AbstractBool::True)], &mut Summaries::new()).unwrap();
+
+            // The loop body and the loop exit are both reachable.
+            assert!(analysis.entry_states.iter().all(|s| s.is_some()));
+
+            // `c` is true on loop entry but false after the first iteration, so the loop head
+            // must see both values.
+            let loop_heads = get_loop_heads(&body);
+            assert_eq!(loop_heads.len(), 1);
+            for head in loop_heads {
+                let state = analysis.entry_states[head].as_ref().unwrap();
+                assert_eq!(state.get(&2), Some(&AbstractValue::Bool(AbstractBool::Top)));
+            }
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn narrowing_recovers_loop_bounds() {
+        let body = r"fn count() -> u32 { let mut i = 0; while i < 10 { i += 1; } i }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let analysis = interpret_body(&body, &vec![], &mut Summaries::new()).unwrap();
+            // Widening alone would only bound the counter by the maximum u32. Narrowing recovers
+            // the bound of the loop condition.
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::UintInterval(UintTy::U32, Interval::from(10))));
+            assert!(analysis.alarms.is_empty());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn narrowing_reaches_the_end_of_long_exit_paths() {
+        // Each addition is checked for overflow in a block of its own, so the return is several
+        // blocks away from the loop exit.
+        let body = r"fn count() -> u32 { let mut i = 0; while i < 10 { i += 1; } let j = i + 1; let k = j + 1; k + 1 }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let analysis = interpret_body(&body, &vec![], &mut Summaries::new()).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::UintInterval(UintTy::U32, Interval::from(13))));
+            assert!(analysis.alarms.is_empty());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn calls_use_callee_summaries() {
+        let body = r"
+            pub fn caller(x: u8) -> u8 { small(x) }
+            fn small(x: u8) -> u8 { x / 100 }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 2);
+
+            // The callee is ordered before its caller
+            let order: Vec<String> = bottom_up_order(&items).iter().map(|item| item.name()).collect();
+            assert_eq!(order, vec!["small".to_string(), "caller".to_string()]);
+
+            let mut summaries = Summaries::new();
+            summaries.insert("small".to_string(), AbstractFunction {
+                arguments: vec![AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255))],
+                return_val: AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 2)),
+                contexts: vec![],
+            });
+            let caller = items.iter().find(|item| item.name() == "caller").unwrap();
+            let args = vec![AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255))];
+            let analysis = interpret_body(&caller.body(), &args, &mut summaries).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 2))));
+
+            // Without a summary the callee could return any u8
+            let analysis = interpret_body(&caller.body(), &args, &mut Summaries::new()).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255))));
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn diverging_functions_return_bottom() {
+        let body = r"
+            pub fn stop(_x: u8) -> ! { panic!() }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let mut summaries = Summaries::with_items(&items, DEFAULT_CONTEXT_LIMIT);
+            let (summary, _) = analyze_function(&items[0].body(), &mut summaries).unwrap();
+            assert!(summary.return_val.is_bottom());
+
+            // Calls with any argument never return
+            summaries.insert("stop".to_string(), summary);
+            let x = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 10));
+            assert!(summaries.call("stop", &[x]).unwrap().is_bottom());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn calls_are_analyzed_per_context() {
+        let body = r"
+            pub fn both(x: i32, y: i32) -> (i32, i32) { (cap(x), cap(y)) }
+            fn cap(x: i32) -> i32 { if x > 50 { 50 } else { x } }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 2);
+
+            let cap_summary = || AbstractFunction {
+                arguments: vec![AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(i32::MIN as i128, i32::MAX as i128))],
+                return_val: AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(i32::MIN as i128, 50)),
+                contexts: vec![],
+            };
+            let both = items.iter().find(|item| item.name() == "both").unwrap();
+            let args = vec![
+                AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(0, 5)),
+                AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(100, 200)),
+            ];
+
+            // Each call site gets the result for its own arguments
+            let mut summaries = Summaries::with_items(&items, 2);
+            summaries.insert("cap".to_string(), cap_summary());
+            let analysis = interpret_body(&both.body(), &args, &mut summaries).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::Tuple(vec![
+                AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(0, 5)),
+                AbstractValue::IntInterval(IntTy::I32, Interval::from(50)),
+            ])));
+            assert_eq!(summaries.summaries["cap"].contexts.len(), 2);
+
+            // Once the limit is reached, calls use the general summary
+            let mut summaries = Summaries::with_items(&items, 1);
+            summaries.insert("cap".to_string(), cap_summary());
+            let analysis = interpret_body(&both.body(), &args, &mut summaries).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::Tuple(vec![
+                AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(0, 5)),
+                AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(i32::MIN as i128, 50)),
+            ])));
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn only_final_call_contexts_are_recorded() {
+        let body = r"
+            pub fn count() -> u32 {
+                let mut i = 0;
+                let mut last = 0;
+                while i < 10 {
+                    last = half(i);
+                    i += 1;
+                }
+                last
+            }
+            fn half(x: u32) -> u32 { x / 2 }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            // The counter is [0, 0] on the first visit of the call and widened afterwards, but only
+            // its value at the fixpoint makes it into a context.
+            let analysis = analyze_crate();
+            let contexts = &analysis.summaries["half"].contexts;
+            assert_eq!(contexts.len(), 1);
+            assert_ne!(contexts[0].arguments, vec![AbstractValue::UintInterval(UintTy::U32, Interval::from(0))]);
+            assert!(AbstractValue::UintInterval(UintTy::U32, Interval::from_interval(0, 9)).leq(&contexts[0].arguments[0]));
+
+            let analysis = analyze_crate_with(0);
+            assert!(analysis.summaries["half"].contexts.is_empty());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn summaries_compute_return_values() {
+        let body = r"
+            pub fn cap(x: u8) -> u8 { if x > 100 { 100 } else { x } }
+            pub fn never(x: u8) -> u8 { loop {} }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 2);
+
+            let cap = items.iter().find(|item| item.name() == "cap").unwrap();
+            let (summary, _) = analyze_function(&cap.body(), &mut Summaries::new()).unwrap();
+            assert_eq!(summary.arguments, vec![AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255))]);
+            assert_eq!(summary.return_val, AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 100)));
+
+            // A function that never returns has a bottom return value
+            let never = items.iter().find(|item| item.name() == "never").unwrap();
+            let (summary, _) = analyze_function(&never.body(), &mut Summaries::new()).unwrap();
+            assert!(summary.return_val.is_bottom());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn return_value_only_joins_feasible_branches() {
+        let body = r"fn negate(b: bool) -> bool { if b { false } else { true } }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let analysis = interpret_body(&body, &vec![AbstractValue::Bool(AbstractBool::True)], &mut Summaries::new()).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::Bool(AbstractBool::False)));
+
+            // The else branch can't be taken so it must never be reached.
+            assert!(analysis.entry_states.iter().any(|s| s.is_none()));
+
+            let analysis = interpret_body(&body, &vec![AbstractValue::Bool(AbstractBool::Top)], &mut Summaries::new()).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::Bool(AbstractBool::Top)));
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn branches_refine_compared_values() {
+        let body = r"fn min(x: i32, y: i32) -> i32 { if x < y { x } else { y } }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let args = vec![
+                AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(0, 20)),
+                AbstractValue::IntInterval(IntTy::I32, Interval::from(10)),
+            ];
+            let analysis = interpret_body(&body, &args, &mut Summaries::new()).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            // x is refined to [0, 9] in the true branch, and y is [10, 10] in the false branch.
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::IntInterval(IntTy::I32, Interval::from_interval(0, 10))));
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn integer_constants_refine_branches() {
+        let body = r"fn at_least_ten(x: u8) -> u8 { if x < 10 { 10 } else { x } }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let analysis = interpret_body(&body, &vec![AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255))], &mut Summaries::new()).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(10, 255))));
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn additions_that_may_overflow_raise_alarms() {
+        let body = r"fn add(x: u8, y: u8) -> u8 { x + y }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let operand = |lower, upper| AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(lower, upper));
+
+            let analysis = interpret_body(&body, &vec![operand(100, 200), operand(50, 100)], &mut Summaries::new()).unwrap();
+            assert_eq!(analysis.alarms.len(), 1);
+            assert_eq!(analysis.alarms[0].kind, AlarmKind::Overflow(smir::mir::BinOp::Add));
+            assert_eq!(analysis.alarms[0].verdict, Verdict::MayFail);
+
+            let analysis = interpret_body(&body, &vec![operand(0, 100), operand(0, 100)], &mut Summaries::new()).unwrap();
+            assert!(analysis.alarms.is_empty());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn overflow_checks_read_through_projections() {
+        let body = r"fn add_one(x: u8) -> u8 { x + 1 }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let analysis = interpret_body(&body, &vec![AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255))], &mut Summaries::new()).unwrap();
+            assert_eq!(analysis.alarms.len(), 1);
+            assert_eq!(analysis.alarms[0].kind, AlarmKind::Overflow(smir::mir::BinOp::Add));
+            assert_eq!(analysis.alarms[0].verdict, Verdict::MayFail);
+            // The overflowing execution panics, so it doesn't contribute to the return value.
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(1, 255))));
+
+            let analysis = interpret_body(&body, &vec![AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 254))], &mut Summaries::new()).unwrap();
+            assert!(analysis.alarms.is_empty());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn divisions_by_zero_are_classified() {
+        let body = r"fn div(x: u8, y: u8) -> u8 { x / y }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let x = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(10, 20));
+            let divisor = |lower, upper| AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(lower, upper));
+
+            let analysis = interpret_body(&body, &vec![x.clone(), divisor(0, 0)], &mut Summaries::new()).unwrap();
+            assert_eq!(analysis.alarms.len(), 1);
+            assert_eq!(analysis.alarms[0].kind, AlarmKind::DivisionByZero);
+            assert_eq!(analysis.alarms[0].verdict, Verdict::Fails);
+            assert!(analysis.return_state.is_none());
+
+            let analysis = interpret_body(&body, &vec![x.clone(), divisor(0, 5)], &mut Summaries::new()).unwrap();
+            assert_eq!(analysis.alarms.len(), 1);
+            assert_eq!(analysis.alarms[0].verdict, Verdict::MayFail);
+
+            let analysis = interpret_body(&body, &vec![x, divisor(1, 5)], &mut Summaries::new()).unwrap();
+            assert!(analysis.alarms.is_empty());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn bounds_checks_compare_index_and_length() {
+        let body = r"fn get(a: [u8; 4], i: usize) -> u8 { a[i] }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let array = AbstractValue::Array(Box::new(AbstractValue::UintInterval(UintTy::U8, Interval::from(7))), Interval::from(4));
+            let index = |lower, upper| AbstractValue::UintInterval(UintTy::Usize, Interval::from_interval(lower, upper));
+
+            let analysis = interpret_body(&body, &vec![array.clone(), index(0, 3)], &mut Summaries::new()).unwrap();
+            assert!(analysis.alarms.is_empty());
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&AbstractValue::UintInterval(UintTy::U8, Interval::from(7))));
+
+            let analysis = interpret_body(&body, &vec![array.clone(), index(2, 6)], &mut Summaries::new()).unwrap();
+            assert_eq!(analysis.alarms.len(), 1);
+            assert_eq!(analysis.alarms[0].kind, AlarmKind::BoundsCheck);
+            assert_eq!(analysis.alarms[0].verdict, Verdict::MayFail);
+
+            let analysis = interpret_body(&body, &vec![array, index(4, 6)], &mut Summaries::new()).unwrap();
+            assert_eq!(analysis.alarms.len(), 1);
+            assert_eq!(analysis.alarms[0].verdict, Verdict::Fails);
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn panic_sites_are_classified_by_reachability() {
+        let body = r"
+            fn check(x: u8) { assert!(x < 10); }
+            fn check_sum(x: u8, y: u8) { assert!(x + y < 10); }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 2);
+
+            let body = items.iter().find(|item| item.name() == "check").unwrap().body();
+            let reachability = |lower, upper| {
+                let arg = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(lower, upper));
+                let analysis = interpret_body(&body, &vec![arg], &mut Summaries::new()).unwrap();
+                panic_sites(&body, &analysis).into_iter().map(|site| site.reachability).collect::<Vec<_>>()
+            };
+
+            assert_eq!(reachability(0, 5), vec![Reachability::Unreachable]);
+            assert_eq!(reachability(20, 30), vec![Reachability::Reachable]);
+            assert_eq!(reachability(0, 30), vec![Reachability::Unknown]);
+
+            // A check that may fail earlier on the path means the panic may not be reached
+            let sum = items.iter().find(|item| item.name() == "check_sum").unwrap().body();
+            let reachability = |upper| {
+                let arg = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(20, upper));
+                let analysis = interpret_body(&sum, &vec![arg.clone(), arg], &mut Summaries::new()).unwrap();
+                panic_sites(&sum, &analysis).into_iter().map(|site| site.reachability).collect::<Vec<_>>()
+            };
+            assert_eq!(reachability(30), vec![Reachability::Reachable]);
+            assert_eq!(reachability(250), vec![Reachability::Unknown]);
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn crate_analysis_classifies_panic_macros() {
+        let body = r#"
+            fn check(x: u8) -> u8 {
+                assert!(x < 10, "too big: {}", x);
+                if x > 20 {
+                    panic!("unreachable: {}", x);
+                }
+                x
+            }
+        "#.to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let analysis = analyze_crate();
+            assert_eq!(
+                analysis.summaries["check"].return_val,
+                AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 9)),
+            );
+
+            let mut reachability: Vec<String> = analysis.panics["check"].iter()
+                .map(|site| format!("{:?}", site.reachability))
+                .collect();
+            reachability.sort();
+            assert_eq!(reachability, vec!["Unknown", "Unreachable"]);
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn integer_switches_refine_the_discriminant() {
+        let body = r"
+            pub fn magic(x: u32) -> u32 { match x { 0xDEAD => 1, 0 => 2, _ => 3 } }
+            pub fn not_min(x: i8) -> i8 { match x { i8::MIN => 0, _ => x } }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            let magic = items.iter().find(|item| item.name() == "magic").unwrap().body();
+            let arg = AbstractValue::UintInterval(UintTy::U32, Interval::from_interval(0, u32::MAX as u128));
+            let analysis = interpret_body(&magic, &vec![arg], &mut Summaries::new()).unwrap();
+            let values: Vec<&AbstractValue> = analysis.successors[0].iter().map(|(_, state)| &state[&1]).collect();
+            assert_eq!(values.len(), 3);
+            assert!(values.contains(&&AbstractValue::UintInterval(UintTy::U32, Interval::from(0xDEAD))));
+            assert!(values.contains(&&AbstractValue::UintInterval(UintTy::U32, Interval::from(0))));
+            assert!(values.contains(&&AbstractValue::UintInterval(UintTy::U32, Interval::from_interval(1, u32::MAX as u128))));
+
+            // Switch values of signed types are the raw bits of the value
+            let not_min = items.iter().find(|item| item.name() == "not_min").unwrap().body();
+            let arg = AbstractValue::IntInterval(IntTy::I8, Interval::from_interval(i8::MIN as i128, i8::MAX as i128));
+            let analysis = interpret_body(&not_min, &vec![arg.clone()], &mut Summaries::new()).unwrap();
+            assert_eq!(
+                return_value(&analysis, &arg),
+                AbstractValue::IntInterval(IntTy::I8, Interval::from_interval(i8::MIN as i128 + 1, i8::MAX as i128)),
+            );
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn dead_branches_are_reported() {
+        let body = r"fn sign(x: u32) -> u32 { if x < 5 { 0 } else { 1 } }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let arg = AbstractValue::UintInterval(UintTy::U32, Interval::from_interval(10, 20));
+            let analysis = interpret_body(&body, &vec![arg], &mut Summaries::new()).unwrap();
+            let kinds: Vec<LintKind> = dead_code_lints(&body, &analysis).into_iter().map(|lint| lint.kind).collect();
+            assert_eq!(kinds, vec![LintKind::DeadCode, LintKind::ConstantCondition(false)]);
+
+            let arg = AbstractValue::UintInterval(UintTy::U32, Interval::from_interval(0, 20));
+            let analysis = interpret_body(&body, &vec![arg], &mut Summaries::new()).unwrap();
+            assert!(dead_code_lints(&body, &analysis).is_empty());
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn proven_assertions_are_not_linted() {
+        let body = r#"
+            fn halve(x: u8) -> u8 {
+                let y = x / 2;
+                assert!(y < 200);
+                debug_assert!(y <= 127, "y is {}", y);
+                y
+            }
+        "#.to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let arg = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(0, 255));
+            let analysis = interpret_body(&body, &vec![arg], &mut Summaries::new()).unwrap();
+            assert_eq!(dead_code_lints(&body, &analysis), vec![]);
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn tests_are_generated_at_branch_boundaries() {
+        let body = r"fn sign(x: u32) -> u32 { if x < 5 { 0 } else { 1 } }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let arg = AbstractValue::UintInterval(UintTy::U32, Interval::from_interval(0, u32::MAX as u128));
+            let analysis = interpret_body(&body, &vec![arg], &mut Summaries::new()).unwrap();
+            let inputs: Vec<String> = boundary_tests(&body, &analysis).unwrap().iter()
+                .map(|test| test.arguments[0].to_string())
+                .collect();
+            // The entry boundaries come first, followed by the new boundaries of each branch side
+            assert_eq!(inputs, vec!["0u32", "4294967295u32", "5u32", "4u32"]);
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn generated_tests_call_functions_by_path() {
+        let body = r"
+            pub fn top(x: u8) -> u8 { x / 2 }
+            pub mod nested {
+                pub fn inner(x: u8) -> u8 { x / 2 }
+                fn hidden(x: u8) -> u8 { x / 2 }
+            }
+            pub struct Counter;
+            impl Counter {
+                pub fn step(x: u8) -> u8 { x / 2 }
+            }
+        ".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let analysis = analyze_crate();
+            let mut paths: Vec<&String> = analysis.call_paths.values().collect();
+            paths.sort();
+            assert_eq!(paths, vec!["crate::nested::inner", "crate::top"]);
+
+            // Functions without a path get no tests
+            let rendered = testgen::to_rust_tests(&analysis.tests, &analysis.call_paths);
+            assert!(rendered.contains("let _ = crate::nested::inner(0u8);"), "{rendered}");
+            assert!(rendered.contains("let _ = crate::top(0u8);"), "{rendered}");
+            assert!(!rendered.contains("hidden") && !rendered.contains("step"), "{rendered}");
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+
+    #[test]
+    fn references_are_dereferenced() {
+        let body = r"fn read(x: &u8) -> u8 { *x }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let items = smir::all_local_items();
+            assert_eq!(items.len(), 1);
+
+            let body = items[0].body();
+            let pointee = AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(3, 7));
+            let analysis = interpret_body(&body, &vec![AbstractValue::Ref(Box::new(pointee.clone()))], &mut Summaries::new()).unwrap();
+            let return_state = analysis.return_state.unwrap();
+            assert_eq!(return_state.get(&0), Some(&pointee));
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+}
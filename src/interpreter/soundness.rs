@@ -0,0 +1,157 @@
+//! Differential tests of the soundness of the analysis. Each test compiles a sample function,
+//! summarizes it the way `analyze_crate` does, along with a call context for abstract arguments
+//! covering ranges of inputs, and then runs the same function on concrete inputs drawn from those
+//! ranges. Every concrete return value must be contained in the return value of the summary and in
+//! that of the context.
+//!
+//! The samples are analyzed with overflow checks, e.g. `arith` panics when `x + y` overflows. In
+//! release builds the compiled samples wrap instead, so the tests only run with debug assertions.
+
+use super::*;
+use crate::test_utils::{self, XorShift};
+use smir::ty::{IntTy, UintTy};
+
+extern crate rustc_middle;
+use rustc_middle::ty::TyCtxt;
+
+/// Number of concrete executions per test.
+const SAMPLES: usize = 1000;
+
+/// Types of concrete arguments and return values of sample functions.
+trait Concrete: Copy + std::fmt::Debug {
+    /// Abstract value covering every value in [lower, upper].
+    fn abstraction(lower: Self, upper: Self) -> AbstractValue;
+
+    /// Returns a value in [lower, upper].
+    fn sample(rng: &mut XorShift, lower: Self, upper: Self) -> Self;
+
+    /// Like `sample`, but favors the bounds since that is where arithmetic tends to go wrong.
+    fn draw(rng: &mut XorShift, lower: Self, upper: Self) -> Self {
+        match rng.next_u64() % 8 {
+            0 => lower,
+            1 => upper,
+            _ => Self::sample(rng, lower, upper),
+        }
+    }
+}
+
+macro_rules! impl_concrete {
+    ($($t:ty => $variant:ident($ty:expr, $bits:ty, $range:ident)),* $(,)?) => {
+        $(impl Concrete for $t {
+            fn abstraction(lower: Self, upper: Self) -> AbstractValue {
+                AbstractValue::$variant($ty, Interval::from_interval(lower as $bits, upper as $bits))
+            }
+
+            fn sample(rng: &mut XorShift, lower: Self, upper: Self) -> Self {
+                rng.$range(lower as $bits, upper as $bits) as $t
+            }
+        })*
+    };
+}
+
+impl_concrete! {
+    u8 => UintInterval(UintTy::U8, u128, range_u128),
+    u16 => UintInterval(UintTy::U16, u128, range_u128),
+    u32 => UintInterval(UintTy::U32, u128, range_u128),
+    u64 => UintInterval(UintTy::U64, u128, range_u128),
+    i8 => IntInterval(IntTy::I8, i128, range_i128),
+    i16 => IntInterval(IntTy::I16, i128, range_i128),
+    i32 => IntInterval(IntTy::I32, i128, range_i128),
+    i64 => IntInterval(IntTy::I64, i128, range_i128),
+}
+
+impl Concrete for bool {
+    fn abstraction(lower: Self, upper: Self) -> AbstractValue {
+        AbstractValue::Bool(if lower == upper { AbstractBool::from(lower) } else { AbstractBool::Top })
+    }
+
+    fn sample(rng: &mut XorShift, lower: Self, upper: Self) -> Self {
+        if lower == upper { lower } else { rng.next_u64() % 2 == 1 }
+    }
+}
+
+/// Defines a test that checks the analysis of the function against concrete executions with
+/// arguments drawn from the given inclusive ranges. The function is both compiled into the test,
+/// to run it, and passed as source to rustc, to analyze it.
+macro_rules! soundness_test {
+    ($test:ident, [$($lower:literal ..= $upper:literal),*], fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty $body:block) => {
+        #[test]
+        fn $test() {
+            fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+                fn $name($($arg: $ty),*) -> $ret $body
+
+                let items = smir::all_local_items();
+                let body = items.iter()
+                    .find(|item| item.name() == stringify!($name))
+                    .expect("sample function is a local item")
+                    .body();
+                let mut summaries = Summaries::with_items(&items, DEFAULT_CONTEXT_LIMIT);
+                let (summary, _) = analyze_function(&body, &mut summaries).expect("sample function can be analyzed");
+                let general_return = summary.return_val.clone();
+                summaries.insert(stringify!($name).to_string(), summary);
+                let arguments = vec![$(<$ty as Concrete>::abstraction($lower, $upper)),*];
+                let abstract_return = summaries.call(stringify!($name), &arguments).expect("sample function has a summary");
+
+                let mut rng = XorShift::new(0x5eed);
+                for _ in 0..SAMPLES {
+                    let inputs = ($(<$ty as Concrete>::draw(&mut rng, $lower, $upper),)*);
+                    let ($($arg,)*) = inputs;
+                    // Executions that panic (e.g. on overflow) don't return, so the abstract
+                    // return value doesn't have to cover them.
+                    let Ok(concrete) = std::panic::catch_unwind(|| $name($($arg),*)) else { continue };
+                    let concrete_return = <$ret as Concrete>::abstraction(concrete, concrete);
+                    assert!(
+                        concrete_return.leq(&general_return),
+                        "{}{:?} returned {:?}, which isn't in the return value {} of the summary",
+                        stringify!($name), inputs, concrete, general_return,
+                    );
+                    assert!(
+                        concrete_return.leq(&abstract_return),
+                        "{}{:?} returned {:?}, which isn't in the abstract return value {}",
+                        stringify!($name), inputs, concrete, abstract_return,
+                    );
+                }
+
+                // If we don't continue, the execution process will be interrupted and the test will fail.
+                std::ops::ControlFlow::Continue(())
+            }
+
+            test_utils::mir_test(stringify!(fn $name($($arg: $ty),*) -> $ret $body).to_string(), callback)
+        }
+    };
+}
+
+soundness_test!(unsigned_arithmetic, [0..=100, 0..=50], fn arith(x: u8, y: u8) -> u8 {
+    (x + y) * 2 - y
+});
+
+soundness_test!(signed_branches, [-100..=100, 0..=10], fn signed(x: i32, y: i32) -> i32 {
+    if x < y { y - x } else { x / (y + 1) }
+});
+
+soundness_test!(remainders, [0..=1000, 1..=7], fn rem(x: u16, y: u16) -> u16 {
+    x % y
+});
+
+soundness_test!(negation, [-128..=127], fn neg(x: i8) -> i8 {
+    -x
+});
+
+soundness_test!(loops, [0..=20], fn sum(n: u32) -> u32 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < n {
+        total += i;
+        i += 1;
+    }
+    total
+});
+
+soundness_test!(comparisons, [-50..=50], fn near_zero(x: i16) -> bool {
+    x > -10 && x < 10
+});
+
+soundness_test!(assertions, [0..=20], fn checked(x: u8) -> u8 {
+    assert!(x < 10, "too big: {}", x);
+    x * 20
+});
@@ -1,10 +1,18 @@
 #![feature(rustc_private)]
 #![feature(box_patterns)]
 
+pub mod alarms;
+mod conditions;
+pub mod corpus;
+mod diagnostics;
 pub mod domains;
 pub mod errors;
 pub mod interpreter;
+pub mod lints;
 mod mir_helpers;
+pub mod report;
+pub mod results;
+pub mod testgen;
 
 #[macro_use]
 extern crate rustc_smir;
@@ -31,6 +39,41 @@ pub(crate) mod test_utils {
         f
     }
 
+    /// Small deterministic pseudo-random number generator (xorshift64*), so that randomized tests
+    /// are reproducible.
+    pub(crate) struct XorShift(u64);
+
+    impl XorShift {
+        pub(crate) fn new(seed: u64) -> Self {
+            // The all-zero state is a fixpoint of xorshift
+            XorShift(seed.max(1))
+        }
+
+        pub(crate) fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        }
+
+        pub(crate) fn next_u128(&mut self) -> u128 {
+            (self.next_u64() as u128) << 64 | self.next_u64() as u128
+        }
+
+        /// Returns a value in [lower, upper].
+        pub(crate) fn range_u128(&mut self, lower: u128, upper: u128) -> u128 {
+            match upper - lower {
+                u128::MAX => self.next_u128(),
+                span => lower + self.next_u128() % (span + 1),
+            }
+        }
+
+        /// Returns a value in [lower, upper].
+        pub(crate) fn range_i128(&mut self, lower: i128, upper: i128) -> i128 {
+            lower.wrapping_add(self.range_u128(0, upper.wrapping_sub(lower) as u128) as i128)
+        }
+    }
+
     pub(crate) fn mir_test(program_body: String, callback: fn(TyCtxt<'_>) -> std::ops::ControlFlow<()>) {
         let program = tmp_program(program_body);
 
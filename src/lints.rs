@@ -0,0 +1,42 @@
+//! Lint-like findings about code the analysis proves can never execute. Unlike alarms, these don't
+//! point at runtime errors but at code that is likely a mistake, e.g. `if x < 0` on a `u32`.
+
+extern crate stable_mir as smir;
+
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LintKind {
+    /// Code that no execution reaches.
+    DeadCode,
+    /// A branch condition that has the same value on every execution, e.g. `true` for
+    /// `ConstantCondition(true)`.
+    ConstantCondition(bool),
+}
+
+/// A finding for a single location of a function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub span: smir::ty::Span,
+}
+
+impl LintKind {
+    /// Stable identifier of the kind of finding, e.g. for the rules of a SARIF report.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            LintKind::DeadCode => "dead-code",
+            LintKind::ConstantCondition(_) => "constant-condition",
+        }
+    }
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintKind::DeadCode => write!(f, "unreachable code"),
+            LintKind::ConstantCondition(value) => write!(f, "this condition is always {value}"),
+        }
+    }
+}
@@ -1,5 +1,9 @@
 extern crate stable_mir as smir;
 
+use smir::mir::BasicBlockIdx;
+use smir::CrateDef;
+use std::collections::{HashMap, HashSet};
+
 // TODO(klinvill): Would be more efficient to just return references to the types along with a
 //  lifetime annotation matching that of the `function` argument (instead of implicitly making
 //  copies of the types).
@@ -16,6 +20,128 @@ pub(crate) fn get_fn_types(function: &smir::mir::Body) -> (Vec<smir::ty::Ty>, sm
     return (arg_types, return_type)
 }
 
+/// Returns the blocks that control may flow to after the terminator executes normally. Unwind
+/// (cleanup) edges are not included since the analysis only follows non-panicking paths.
+pub(crate) fn get_successors(terminator: &smir::mir::Terminator) -> Vec<BasicBlockIdx> {
+    match &terminator.kind {
+        smir::mir::TerminatorKind::Goto { target }
+        | smir::mir::TerminatorKind::Drop { target, .. }
+        | smir::mir::TerminatorKind::Assert { target, .. } => vec![*target],
+        smir::mir::TerminatorKind::SwitchInt { targets, .. } => {
+            let mut successors: Vec<BasicBlockIdx> = targets.branches().map(|(_, target)| target).collect();
+            successors.push(targets.otherwise());
+            successors
+        }
+        smir::mir::TerminatorKind::Call { target, .. } => target.iter().cloned().collect(),
+        smir::mir::TerminatorKind::InlineAsm { destination, .. } => destination.iter().cloned().collect(),
+        // Return, Resume, Abort, Unreachable, etc. don't transfer control to another block.
+        _ => vec![],
+    }
+}
+
+/// Returns the name of the function a call operand refers to, or None if the callee isn't known
+/// statically (e.g. a call through a function pointer).
+pub(crate) fn get_callee_name(func: &smir::mir::Operand) -> Option<String> {
+    match func {
+        smir::mir::Operand::Constant(c) => match c.literal.ty().kind() {
+            smir::ty::TyKind::RigidTy(smir::ty::RigidTy::FnDef(def, _)) => Some(def.name()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns true if the function only ever panics, like the functions `panic!`, `assert!`,
+/// `unreachable!` and friends expand to calls of.
+pub(crate) fn is_panic_fn(name: &str) -> bool {
+    name.starts_with("core::panicking::")
+        || name.starts_with("std::panicking::")
+        || name == "std::rt::begin_panic"
+}
+
+/// Returns the names of the functions called directly from the body.
+pub(crate) fn get_callees(body: &smir::mir::Body) -> Vec<String> {
+    body.blocks.iter()
+        .filter_map(|block| match &block.terminator.kind {
+            smir::mir::TerminatorKind::Call { func, .. } => get_callee_name(func),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Orders the items so that every function comes after the local functions it calls, i.e. in
+/// post-order of the call graph. Functions in a recursion cycle are ordered arbitrarily among
+/// themselves.
+pub(crate) fn bottom_up_order(items: &[smir::CrateItem]) -> Vec<smir::CrateItem> {
+    fn visit(item: &smir::CrateItem, by_name: &HashMap<String, &smir::CrateItem>, visited: &mut HashSet<String>, order: &mut Vec<smir::CrateItem>) {
+        if !visited.insert(item.name()) {
+            return;
+        }
+        for callee in get_callees(&item.body()) {
+            if let Some(callee_item) = by_name.get(&callee) {
+                visit(callee_item, by_name, visited, order);
+            }
+        }
+        order.push(item.clone());
+    }
+
+    let by_name: HashMap<String, &smir::CrateItem> = items.iter().map(|item| (item.name(), item)).collect();
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    for item in items {
+        visit(item, &by_name, &mut visited, &mut order);
+    }
+    order
+}
+
+/// Returns the locals that are mutably borrowed (or have a mutable raw pointer taken) anywhere in the
+/// body. Any of these may be modified by a write through a reference or by a function call.
+pub(crate) fn mutably_borrowed_locals(body: &smir::mir::Body) -> HashSet<smir::mir::Local> {
+    body.blocks.iter()
+        .flat_map(|block| &block.statements)
+        .filter_map(|statement| match &statement.kind {
+            smir::mir::StatementKind::Assign(_, smir::mir::Rvalue::Ref(_, smir::mir::BorrowKind::Mut { .. }, place))
+            | smir::mir::StatementKind::Assign(_, smir::mir::Rvalue::AddressOf(smir::mir::Mutability::Mut, place)) => Some(place.local),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the blocks that are the target of a back edge in the control-flow graph, i.e. the heads
+/// of loops. These are the blocks where widening needs to be applied for the analysis to terminate.
+pub(crate) fn get_loop_heads(body: &smir::mir::Body) -> HashSet<BasicBlockIdx> {
+    let mut loop_heads = HashSet::new();
+    if body.blocks.is_empty() {
+        return loop_heads;
+    }
+
+    // Iterative depth-first search from the entry block. An edge to a block that is still on the
+    // DFS stack closes a cycle, so that block is a loop head.
+    let mut visited = vec![false; body.blocks.len()];
+    let mut on_stack = vec![false; body.blocks.len()];
+    let mut stack = vec![(0, get_successors(&body.blocks[0].terminator), 0)];
+    visited[0] = true;
+    on_stack[0] = true;
+
+    while let Some((bb, successors, next)) = stack.last_mut() {
+        if let Some(&succ) = successors.get(*next) {
+            *next += 1;
+            if on_stack[succ] {
+                loop_heads.insert(succ);
+            } else if !visited[succ] {
+                visited[succ] = true;
+                on_stack[succ] = true;
+                stack.push((succ, get_successors(&body.blocks[succ].terminator), 0));
+            }
+        } else {
+            on_stack[*bb] = false;
+            stack.pop();
+        }
+    }
+
+    loop_heads
+}
+
 
 #[cfg(test)]
 mod tests {
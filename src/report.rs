@@ -0,0 +1,473 @@
+//! Machine-readable output of the analysis results, as JSON or as SARIF 2.1.0.
+//!
+//! Abstract values are serialized with their `Display` representation, e.g. `[0, 255]` for an
+//! interval or `top` for a boolean that may be either value. The functions here read source
+//! locations from Stable MIR spans, so they have to be called from within the callback of `run!`.
+
+extern crate stable_mir as smir;
+
+use crate::alarms::{Alarm, PanicSite, Reachability, Verdict};
+use crate::domains::{AbstractFunction, CallContext};
+use crate::interpreter::{Invariant, ProgramAnalysis};
+use crate::lints::Lint;
+use crate::testgen::TestCase;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// How `analyze_program_with` reports its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Through rustc's diagnostics: errors for checks that always fail, warnings for checks that
+    /// may fail and for lints.
+    Diagnostics,
+    Json,
+    Sarif,
+    /// `#[test]` functions calling each function with inputs at the boundaries of its branches.
+    Tests,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "diagnostics" => Ok(OutputFormat::Diagnostics),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "tests" => Ok(OutputFormat::Tests),
+            _ => Err(format!("unknown output format `{s}`, expected one of: diagnostics, json, sarif, tests")),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub functions: Vec<FunctionReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionReport {
+    pub name: String,
+    pub summary: Option<SummaryReport>,
+    pub invariants: Vec<InvariantReport>,
+    pub alarms: Vec<AlarmReport>,
+    pub panics: Vec<PanicReport>,
+    pub lints: Vec<LintReport>,
+    /// Boundary inputs, see `testgen`.
+    pub tests: Vec<TestCase>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SummaryReport {
+    pub arguments: Vec<String>,
+    pub return_val: String,
+    pub contexts: Vec<ContextReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContextReport {
+    pub arguments: Vec<String>,
+    pub return_val: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvariantReport {
+    pub block: usize,
+    pub location: Location,
+    /// Abstract values by local, e.g. `_1`.
+    pub values: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AlarmReport {
+    pub rule: &'static str,
+    pub verdict: Verdict,
+    pub message: String,
+    pub location: Location,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PanicReport {
+    pub callee: String,
+    pub reachability: Reachability,
+    pub location: Location,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    pub rule: &'static str,
+    pub message: String,
+    pub location: Location,
+}
+
+/// Source region, with 1-based lines and columns.
+#[derive(Debug, Serialize)]
+pub struct Location {
+    pub file: String,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl Location {
+    fn from_span(span: &smir::ty::Span) -> Self {
+        let lines = span.get_lines();
+        Location {
+            file: span.get_filename(),
+            start_line: lines.start_line,
+            start_col: lines.start_col,
+            end_line: lines.end_line,
+            end_col: lines.end_col,
+        }
+    }
+}
+
+impl From<&AbstractFunction> for SummaryReport {
+    fn from(summary: &AbstractFunction) -> Self {
+        SummaryReport {
+            arguments: summary.arguments.iter().map(|arg| arg.to_string()).collect(),
+            return_val: summary.return_val.to_string(),
+            contexts: summary.contexts.iter().map(ContextReport::from).collect(),
+        }
+    }
+}
+
+impl From<&CallContext> for ContextReport {
+    fn from(context: &CallContext) -> Self {
+        ContextReport {
+            arguments: context.arguments.iter().map(|arg| arg.to_string()).collect(),
+            return_val: context.return_val.to_string(),
+        }
+    }
+}
+
+impl From<&Invariant> for InvariantReport {
+    fn from(invariant: &Invariant) -> Self {
+        InvariantReport {
+            block: invariant.block,
+            location: Location::from_span(&invariant.span),
+            values: invariant.values.iter().map(|(local, value)| (format!("_{local}"), value.to_string())).collect(),
+        }
+    }
+}
+
+impl From<&Alarm> for AlarmReport {
+    fn from(alarm: &Alarm) -> Self {
+        AlarmReport {
+            rule: alarm.kind.rule_id(),
+            verdict: alarm.verdict,
+            message: alarm.kind.to_string(),
+            location: Location::from_span(&alarm.span),
+        }
+    }
+}
+
+impl From<&PanicSite> for PanicReport {
+    fn from(panic: &PanicSite) -> Self {
+        PanicReport {
+            callee: panic.callee.clone(),
+            reachability: panic.reachability,
+            location: Location::from_span(&panic.span),
+        }
+    }
+}
+
+impl From<&Lint> for LintReport {
+    fn from(lint: &Lint) -> Self {
+        LintReport {
+            rule: lint.kind.rule_id(),
+            message: lint.kind.to_string(),
+            location: Location::from_span(&lint.span),
+        }
+    }
+}
+
+impl From<&ProgramAnalysis> for Report {
+    fn from(analysis: &ProgramAnalysis) -> Self {
+        let mut names: Vec<&String> = analysis.summaries.keys()
+            .chain(analysis.alarms.keys())
+            .chain(analysis.invariants.keys())
+            .chain(analysis.panics.keys())
+            .chain(analysis.lints.keys())
+            .chain(analysis.tests.keys())
+            .collect();
+        names.sort();
+        names.dedup();
+
+        let functions = names.into_iter()
+            .map(|name| FunctionReport {
+                name: name.clone(),
+                summary: analysis.summaries.get(name).map(SummaryReport::from),
+                invariants: analysis.invariants.get(name).into_iter().flatten().map(InvariantReport::from).collect(),
+                alarms: analysis.alarms.get(name).into_iter().flatten().map(AlarmReport::from).collect(),
+                panics: analysis.panics.get(name).into_iter().flatten().map(PanicReport::from).collect(),
+                lints: analysis.lints.get(name).into_iter().flatten().map(LintReport::from).collect(),
+                tests: analysis.tests.get(name).cloned().unwrap_or_default(),
+            })
+            .collect();
+        Report { functions }
+    }
+}
+
+/// Serializes the summaries, invariants, alarms, panic sites, lints and boundary test inputs of
+/// every function.
+pub fn to_json(analysis: &ProgramAnalysis) -> String {
+    serde_json::to_string_pretty(&Report::from(analysis)).expect("reports only contain serializable data")
+}
+
+/// Serializes the alarms, lints and panic sites as a SARIF 2.1.0 log with a single run.
+pub fn to_sarif(analysis: &ProgramAnalysis) -> String {
+    serde_json::to_string_pretty(&sarif_log(&Report::from(analysis))).expect("reports only contain serializable data")
+}
+
+fn sarif_log(report: &Report) -> serde_json::Value {
+    // (function, rule, level, message, location) of every result
+    let findings: Vec<(&str, &'static str, &str, String, &Location)> = report.functions.iter()
+        .flat_map(|function| {
+            let alarms = function.alarms.iter().map(move |alarm| {
+                let level = match alarm.verdict {
+                    Verdict::Fails => "error",
+                    Verdict::MayFail => "warning",
+                };
+                (function.name.as_str(), alarm.rule, level, alarm.message.clone(), &alarm.location)
+            });
+            let lints = function.lints.iter()
+                .map(move |lint| (function.name.as_str(), lint.rule, "warning", lint.message.clone(), &lint.location));
+            let panics = function.panics.iter().map(move |panic| {
+                let (level, message) = match panic.reachability {
+                    Reachability::Reachable => ("error", format!("`{}` is called on every execution", panic.callee)),
+                    Reachability::Unknown => ("warning", format!("`{}` may be called", panic.callee)),
+                    Reachability::Unreachable => ("note", format!("`{}` is never called", panic.callee)),
+                };
+                (function.name.as_str(), PanicSite::RULE_ID, level, message, &panic.location)
+            });
+            alarms.chain(lints).chain(panics)
+        })
+        .collect();
+
+    let mut rules: Vec<&'static str> = findings.iter().map(|(_, rule, ..)| *rule).collect();
+    rules.sort();
+    rules.dedup();
+
+    let results: Vec<serde_json::Value> = findings.iter()
+        .map(|(function, rule, level, message, location)| serde_json::json!({
+            "ruleId": rule,
+            "level": level,
+            "message": { "text": message },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": artifact_location(&location.file),
+                    "region": {
+                        "startLine": location.start_line,
+                        "startColumn": location.start_col,
+                        "endLine": location.end_line,
+                        "endColumn": location.end_col,
+                    },
+                },
+                "logicalLocations": [{ "fullyQualifiedName": function, "kind": "function" }],
+            }],
+        }))
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|id| serde_json::json!({ "id": id })).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// SARIF artifact location of a source file. Absolute paths become `file` URIs, other paths are
+/// relative references resolved against the source root, which is where rustc was run from.
+fn artifact_location(file: &str) -> serde_json::Value {
+    let path = file.replace('\\', "/");
+    if path.starts_with('/') {
+        serde_json::json!({ "uri": format!("file://{}", percent_encode(&path, true)) })
+    } else if path.as_bytes().get(1) == Some(&b':') {
+        // Windows path with a drive letter, e.g. `C:/src/lib.rs`
+        serde_json::json!({ "uri": format!("file:///{}", percent_encode(&path, true)) })
+    } else {
+        serde_json::json!({ "uri": percent_encode(&path, false), "uriBaseId": "%SRCROOT%" })
+    }
+}
+
+/// Escapes the bytes of a path that can't appear in a URI path as is. Colons are only kept in
+/// absolute paths, since in a relative reference they would be read as the end of a scheme.
+fn percent_encode(path: &str, keep_colons: bool) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => encoded.push(byte as char),
+            b':' if keep_colons => encoded.push(':'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::analyze_crate;
+    use crate::test_utils;
+    use crate::testgen::{Argument, ArgumentType, Branch, ConcreteValue};
+    use smir::ty::UintTy;
+    use serde_json::json;
+
+    extern crate rustc_middle;
+    use rustc_middle::ty::TyCtxt;
+
+    fn location(start_line: usize) -> Location {
+        Location { file: "src/lib.rs".to_string(), start_line, start_col: 5, end_line: start_line, end_col: 10 }
+    }
+
+    /// A report with one of each kind of result, as the analysis of
+    /// `fn div(x: u8, y: u8) -> u8 { assert!(y < 10); if y == 0 { 0 } else { x / y } }` could give.
+    fn sample_report() -> Report {
+        Report {
+            functions: vec![FunctionReport {
+                name: "div".to_string(),
+                summary: Some(SummaryReport {
+                    arguments: vec!["[0, 255]".to_string(), "[0, 255]".to_string()],
+                    return_val: "[0, 255]".to_string(),
+                    contexts: vec![],
+                }),
+                invariants: vec![InvariantReport {
+                    block: 0,
+                    location: location(1),
+                    values: BTreeMap::from([("_2".to_string(), "[0, 255]".to_string())]),
+                }],
+                alarms: vec![AlarmReport {
+                    rule: "division-by-zero",
+                    verdict: Verdict::MayFail,
+                    message: "division by zero".to_string(),
+                    location: location(3),
+                }],
+                panics: vec![PanicReport {
+                    callee: "core::panicking::panic".to_string(),
+                    reachability: Reachability::Unknown,
+                    location: location(2),
+                }],
+                lints: vec![LintReport {
+                    rule: "dead-code",
+                    message: "unreachable code".to_string(),
+                    location: location(4),
+                }],
+                tests: vec![TestCase {
+                    branch: Some(Branch { block: 1, target: 2 }),
+                    arguments: vec![
+                        Argument { ty: ArgumentType::Uint(UintTy::U8), value: ConcreteValue::Uint(0) },
+                        Argument { ty: ArgumentType::Uint(UintTy::U8), value: ConcreteValue::Uint(9) },
+                    ],
+                }],
+            }],
+        }
+    }
+
+    fn golden_location(start_line: usize) -> serde_json::Value {
+        json!({ "file": "src/lib.rs", "start_line": start_line, "start_col": 5, "end_line": start_line, "end_col": 10 })
+    }
+
+    fn golden_result(rule: &str, level: &str, message: &str, start_line: usize) -> serde_json::Value {
+        json!({
+            "ruleId": rule,
+            "level": level,
+            "message": { "text": message },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "%SRCROOT%" },
+                    "region": { "startLine": start_line, "startColumn": 5, "endLine": start_line, "endColumn": 10 },
+                },
+                "logicalLocations": [{ "fullyQualifiedName": "div", "kind": "function" }],
+            }],
+        })
+    }
+
+    #[test]
+    fn json_reports_match_golden() {
+        let expected = json!({
+            "functions": [{
+                "name": "div",
+                "summary": { "arguments": ["[0, 255]", "[0, 255]"], "return_val": "[0, 255]", "contexts": [] },
+                "invariants": [{ "block": 0, "location": golden_location(1), "values": { "_2": "[0, 255]" } }],
+                "alarms": [{
+                    "rule": "division-by-zero",
+                    "verdict": "MayFail",
+                    "message": "division by zero",
+                    "location": golden_location(3),
+                }],
+                "panics": [{ "callee": "core::panicking::panic", "reachability": "Unknown", "location": golden_location(2) }],
+                "lints": [{ "rule": "dead-code", "message": "unreachable code", "location": golden_location(4) }],
+                "tests": [{
+                    "branch": { "block": 1, "target": 2 },
+                    "arguments": [{ "type": "u8", "value": 0 }, { "type": "u8", "value": 9 }],
+                }],
+            }],
+        });
+        assert_eq!(serde_json::to_value(sample_report()).unwrap(), expected);
+    }
+
+    #[test]
+    fn sarif_logs_match_golden() {
+        let expected = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": [{ "id": "dead-code" }, { "id": "division-by-zero" }, { "id": "explicit-panic" }],
+                    },
+                },
+                "results": [
+                    golden_result("division-by-zero", "warning", "division by zero", 3),
+                    golden_result("dead-code", "warning", "unreachable code", 4),
+                    golden_result("explicit-panic", "warning", "`core::panicking::panic` may be called", 2),
+                ],
+            }],
+        });
+        assert_eq!(sarif_log(&sample_report()), expected);
+    }
+
+    #[test]
+    fn artifact_locations_are_uris() {
+        assert_eq!(artifact_location("/home/me/my crate/src/lib.rs"), json!({ "uri": "file:///home/me/my%20crate/src/lib.rs" }));
+        assert_eq!(artifact_location("C:\\src\\lib.rs"), json!({ "uri": "file:///C:/src/lib.rs" }));
+        assert_eq!(artifact_location("src/a:b.rs"), json!({ "uri": "src/a%3Ab.rs", "uriBaseId": "%SRCROOT%" }));
+    }
+
+    #[test]
+    fn analyses_are_serialized() {
+        let body = r"fn div(x: u8, y: u8) -> u8 { x / y }".to_string();
+        fn callback(_: TyCtxt<'_>) -> std::ops::ControlFlow<()> {
+            let analysis = analyze_crate();
+
+            let report: serde_json::Value = serde_json::from_str(&to_json(&analysis)).unwrap();
+            assert_eq!(report["functions"][0]["name"], "div");
+            assert_eq!(report["functions"][0]["alarms"][0]["rule"], "division-by-zero");
+
+            let log: serde_json::Value = serde_json::from_str(&to_sarif(&analysis)).unwrap();
+            assert_eq!(log["version"], "2.1.0");
+            let result = &log["runs"][0]["results"][0];
+            assert_eq!(result["ruleId"], "division-by-zero");
+            assert_eq!(result["level"], "warning");
+            let uri = result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"].as_str().unwrap();
+            assert!(uri.starts_with("file:///") && uri.ends_with(".rs"), "{uri} isn't a file URI");
+
+            // If we don't continue, the execution process will be interrupted and the test will fail.
+            std::ops::ControlFlow::Continue(())
+        }
+
+        test_utils::mir_test(body, callback)
+    }
+}
@@ -0,0 +1,43 @@
+//! Exchange of analysis results between the driver, which cargo runs once per compiled crate, and
+//! `cargo-absint`, which aggregates the results of all crates of a workspace.
+
+use crate::interpreter::ProgramAnalysis;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the directory the driver writes the results of each crate to.
+/// The driver only runs in cargo wrapper mode when it is set.
+pub const RESULTS_DIR_ENV: &str = "ABSINT_RESULTS_DIR";
+
+/// Environment variable holding the number of call contexts the driver keeps per function, for
+/// when the driver is run by cargo and can't be given flags of its own.
+pub const CONTEXT_LIMIT_ENV: &str = "ABSINT_CONTEXT_LIMIT";
+
+/// Path of the results file for a compilation unit. Cargo gives every unit (e.g. the lib and the
+/// test target of a package) a distinct `-C extra-filename`, which also ends up in the names of
+/// the artifacts cargo reports, e.g. `libfoo-1a2b3c.rmeta` for crate `foo` with extra filename
+/// `-1a2b3c`. `unit_name` is that file stem without the `lib` prefix, e.g. `foo-1a2b3c`.
+pub fn results_path(results_dir: &Path, unit_name: &str) -> PathBuf {
+    results_dir.join(format!("{unit_name}.txt"))
+}
+
+/// Writes the alarms of an analyzed crate, one per line.
+pub fn write_results(path: &Path, analysis: &ProgramAnalysis) -> io::Result<()> {
+    let mut functions: Vec<&String> = analysis.alarms.keys().collect();
+    functions.sort();
+    let lines: Vec<String> = functions.into_iter()
+        .flat_map(|func| analysis.alarms[func].iter().map(move |alarm| format!("{func}: {alarm}")))
+        .collect();
+    let mut contents = lines.join("\n");
+    if !contents.is_empty() {
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+}
+
+/// Reads the alarms written by `write_results`. Returns None if the unit wasn't analyzed.
+pub fn read_results(path: &Path) -> Option<Vec<String>> {
+    let contents = fs::read_to_string(path).ok()?;
+    Some(contents.lines().map(str::to_string).collect())
+}
@@ -0,0 +1,197 @@
+//! Generation of test inputs from the results of the analysis. For every feasible side of a branch,
+//! the arguments are picked at the boundaries of the intervals that reach it, which is where
+//! off-by-one mistakes show up. The inputs are emitted either as `#[test]` functions that call the
+//! function under test, or as part of the JSON report.
+
+extern crate stable_mir as smir;
+
+use crate::domains::booleans::AbstractBool;
+use crate::domains::interval::{Interval, IntervalElem};
+use crate::domains::{int_bounds, uint_bounds, AbstractDomain, AbstractValue};
+use serde::Serialize;
+use smir::ty::{IntTy, UintTy};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ConcreteValue {
+    Bool(bool),
+    Int(i128),
+    Uint(u128),
+}
+
+/// Types of arguments we can write literals for. Serialized as the name of the type, e.g. `u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentType {
+    Bool,
+    Int(IntTy),
+    Uint(UintTy),
+}
+
+/// A concrete argument along with its type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Argument {
+    #[serde(rename = "type")]
+    pub ty: ArgumentType,
+    pub value: ConcreteValue,
+}
+
+impl ArgumentType {
+    pub fn name(self) -> &'static str {
+        match self {
+            ArgumentType::Bool => "bool",
+            ArgumentType::Int(ty) => int_name(ty),
+            ArgumentType::Uint(ty) => uint_name(ty),
+        }
+    }
+}
+
+impl Serialize for ArgumentType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// The edge from a block to one of its successors that a test targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Branch {
+    pub block: usize,
+    pub target: usize,
+}
+
+/// Concrete arguments for a call to the function under test.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestCase {
+    /// The branch the arguments were derived from, or None for the boundaries of the argument
+    /// types themselves.
+    pub branch: Option<Branch>,
+    pub arguments: Vec<Argument>,
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            ConcreteValue::Bool(b) => write!(f, "{b}"),
+            ConcreteValue::Int(i) => write!(f, "{i}{}", self.ty.name()),
+            ConcreteValue::Uint(u) => write!(f, "{u}{}", self.ty.name()),
+        }
+    }
+}
+
+/// Returns the argument vectors at the lower and at the upper boundaries of the abstract arguments,
+/// or None if an argument has a type we can't write a literal for (e.g. a reference). Returns no
+/// vectors if an argument is bottom.
+pub(crate) fn boundary_arguments(arguments: &[AbstractValue]) -> Option<Vec<Vec<Argument>>> {
+    let boundaries: Vec<Vec<Argument>> = arguments.iter().map(boundary_values).collect::<Option<_>>()?;
+    if boundaries.iter().any(Vec::is_empty) {
+        return Some(vec![]);
+    }
+    let lower: Vec<Argument> = boundaries.iter().map(|values| values[0].clone()).collect();
+    let upper: Vec<Argument> = boundaries.iter().map(|values| values[values.len() - 1].clone()).collect();
+    if lower == upper {
+        Some(vec![lower])
+    } else {
+        Some(vec![lower, upper])
+    }
+}
+
+/// The smallest and largest concrete values of an abstract value, in that order.
+fn boundary_values(value: &AbstractValue) -> Option<Vec<Argument>> {
+    let values = match value {
+        AbstractValue::Bool(AbstractBool::Bot) => vec![],
+        AbstractValue::Bool(AbstractBool::Top) => vec![ConcreteValue::Bool(false), ConcreteValue::Bool(true)],
+        AbstractValue::Bool(b) => vec![ConcreteValue::Bool(*b == AbstractBool::True)],
+        AbstractValue::IntInterval(ty, i) => bounds(&i.meet(&int_bounds(*ty))).into_iter().map(ConcreteValue::Int).collect(),
+        AbstractValue::UintInterval(ty, i) => bounds(&i.meet(&uint_bounds(*ty))).into_iter().map(ConcreteValue::Uint).collect(),
+        _ => return None,
+    };
+    let ty = match value {
+        AbstractValue::Bool(_) => ArgumentType::Bool,
+        AbstractValue::IntInterval(ty, _) => ArgumentType::Int(*ty),
+        AbstractValue::UintInterval(ty, _) => ArgumentType::Uint(*ty),
+        _ => unreachable!("other values have no boundaries"),
+    };
+    Some(values.into_iter().map(|value| Argument { ty, value }).collect())
+}
+
+/// The bounds of an interval that is within the bounds of its type, and so finite.
+fn bounds<T: Ord + Copy>(interval: &Interval<T>) -> Vec<T> {
+    match (interval.lower, interval.upper) {
+        (IntervalElem::Elem(lower), IntervalElem::Elem(upper)) if lower == upper => vec![lower],
+        (IntervalElem::Elem(lower), IntervalElem::Elem(upper)) => vec![lower, upper],
+        _ => vec![],
+    }
+}
+
+fn int_name(ty: IntTy) -> &'static str {
+    match ty {
+        IntTy::Isize => "isize",
+        IntTy::I8 => "i8",
+        IntTy::I16 => "i16",
+        IntTy::I32 => "i32",
+        IntTy::I64 => "i64",
+        IntTy::I128 => "i128",
+    }
+}
+
+fn uint_name(ty: UintTy) -> &'static str {
+    match ty {
+        UintTy::Usize => "usize",
+        UintTy::U8 => "u8",
+        UintTy::U16 => "u16",
+        UintTy::U32 => "u32",
+        UintTy::U64 => "u64",
+        UintTy::U128 => "u128",
+    }
+}
+
+/// Renders the test cases as `#[test]` functions, one per test case, that call the functions
+/// under test through their paths in `call_paths`. The tests are meant for a test module in the
+/// crate root, and functions without a path are skipped. The tests only check that the calls
+/// don't panic, since the analysis doesn't know the expected results.
+pub fn to_rust_tests(tests: &HashMap<String, Vec<TestCase>>, call_paths: &HashMap<String, String>) -> String {
+    let mut functions: Vec<&String> = tests.keys().filter(|function| call_paths.contains_key(*function)).collect();
+    functions.sort();
+
+    let mut out = String::new();
+    for function in functions {
+        let test_name = function.replace("::", "_");
+        let path = &call_paths[function];
+        for (i, test) in tests[function].iter().enumerate() {
+            let arguments: Vec<String> = test.arguments.iter().map(Argument::to_string).collect();
+            if let Some(branch) = test.branch {
+                out.push_str(&format!("// Reaches bb{} from bb{}\n", branch.target, branch.block));
+            }
+            out.push_str(&format!(
+                "#[test]\nfn absint_{test_name}_{i}() {{\n    let _ = {path}({});\n}}\n\n",
+                arguments.join(", "),
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boundaries_of_arguments() {
+        let arguments = vec![
+            AbstractValue::UintInterval(UintTy::U8, Interval::from_interval(5, 10)),
+            AbstractValue::IntInterval(IntTy::I8, Interval { lower: IntervalElem::NegInf, upper: IntervalElem::Elem(-1) }),
+            AbstractValue::Bool(AbstractBool::True),
+        ];
+        let rendered: Vec<Vec<String>> = boundary_arguments(&arguments).unwrap().iter()
+            .map(|arguments| arguments.iter().map(Argument::to_string).collect())
+            .collect();
+        assert_eq!(rendered, vec![vec!["5u8", "-128i8", "true"], vec!["10u8", "-1i8", "true"]]);
+
+        let bottom = vec![AbstractValue::UintInterval(UintTy::U8, Interval::from(0).bottom())];
+        assert_eq!(boundary_arguments(&bottom), Some(vec![]));
+
+        let reference = vec![AbstractValue::Ref(Box::new(AbstractValue::Bool(AbstractBool::Top)))];
+        assert_eq!(boundary_arguments(&reference), None);
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests of `cargo absint` on a small workspace.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Writes a library package with the given source.
+fn write_package(dir: &Path, name: &str, dependencies: &str, source: &str) {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n{dependencies}"),
+    )
+    .unwrap();
+    fs::write(dir.join("src").join("lib.rs"), source).unwrap();
+}
+
+/// Runs `cargo absint` in the directory and returns its exit status along with the alarms it
+/// reported, sorted.
+fn run_cargo_absint(dir: &Path) -> (Option<i32>, Vec<String>) {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-absint"))
+        .arg("absint")
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    let mut alarms: Vec<String> = String::from_utf8(output.stdout).unwrap()
+        .lines()
+        .filter(|line| line.starts_with("warning: "))
+        .map(str::to_string)
+        .collect();
+    alarms.sort();
+    (output.status.code(), alarms)
+}
+
+#[test]
+fn alarms_of_workspace_crates_are_aggregated() {
+    let root = tempfile::tempdir().unwrap();
+    let workspace = root.path().join("workspace");
+    fs::create_dir_all(&workspace).unwrap();
+    fs::write(workspace.join("Cargo.toml"), "[workspace]\nmembers = [\"clean\", \"overflowing\"]\nresolver = \"2\"\n").unwrap();
+    write_package(
+        &workspace.join("clean"),
+        "clean",
+        "vendored = { path = \"../../vendored\" }\n",
+        "pub fn half(x: u8) -> u8 { vendored::add(x, 0) / 2 }\n",
+    );
+    write_package(&workspace.join("overflowing"), "overflowing", "", "pub fn add(x: u8, y: u8) -> u8 { x + y }\n");
+    // A dependency outside of the workspace, which isn't analyzed even though it has an alarm
+    write_package(&root.path().join("vendored"), "vendored", "", "pub fn add(x: u8, y: u8) -> u8 { x + y }\n");
+
+    let (status, alarms) = run_cargo_absint(&workspace);
+    assert_eq!(status, Some(1));
+    assert_eq!(alarms.len(), 1, "unexpected alarms: {alarms:?}");
+    assert!(alarms[0].starts_with("warning: overflowing (lib): add: possible arithmetic overflow in Add"), "{}", alarms[0]);
+
+    // Nothing changed, so cargo doesn't recompile any crate, but the results of the previous run
+    // are still reported.
+    assert_eq!(run_cargo_absint(&workspace), (Some(1), alarms));
+}
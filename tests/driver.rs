@@ -0,0 +1,96 @@
+//! Tests of the exit status of the `absint` driver.
+
+use std::io::Write;
+use std::process::Command;
+
+/// Runs the driver on a library crate with the given source and returns its exit status.
+fn run_driver(source: &str) -> Option<i32> {
+    run_driver_with(source, &[])
+}
+
+/// Like `run_driver`, with extra flags for the driver.
+fn run_driver_with(source: &str, flags: &[&str]) -> Option<i32> {
+    let mut file = tempfile::Builder::new().prefix("tmp").suffix(".rs").tempfile().unwrap();
+    file.write_all(source.as_bytes()).unwrap();
+    let out_dir = tempfile::tempdir().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_absint"))
+        .arg(file.path())
+        .arg("--crate-type=lib")
+        .arg("--out-dir")
+        .arg(out_dir.path())
+        .args(flags)
+        .status()
+        .unwrap()
+        .code()
+}
+
+#[test]
+fn clean_crates_succeed() {
+    assert_eq!(run_driver("pub fn half(x: u8) -> u8 { x / 2 }"), Some(0));
+}
+
+#[test]
+fn alarms_exit_with_status_1() {
+    // Possible failure
+    assert_eq!(run_driver("pub fn div(x: u8, y: u8) -> u8 { x / y }"), Some(1));
+    // Certain failure, which must not be reported as a failed compilation
+    assert_eq!(run_driver("pub fn div(x: u8, y: u8) -> u8 { if y == 0 { x / y } else { 0 } }"), Some(1));
+}
+
+#[test]
+fn crates_that_dont_compile_exit_with_status_2() {
+    assert_eq!(run_driver("pub fn broken() -> u8 { true }"), Some(2));
+}
+
+#[test]
+fn context_limit_is_checked() {
+    let source = "pub fn half(x: u8) -> u8 { x / 2 }";
+    assert_eq!(run_driver_with(source, &["--absint-contexts=0"]), Some(0));
+    assert_eq!(run_driver_with(source, &["--absint-contexts=many"]), Some(2));
+}
+
+#[test]
+fn driver_flags_can_only_be_given_once() {
+    let source = "pub fn half(x: u8) -> u8 { x / 2 }";
+    assert_eq!(run_driver_with(source, &["--absint-output=json", "--absint-output=sarif"]), Some(2));
+    assert_eq!(run_driver_with(source, &["--absint-contexts=1", "--absint-contexts=2"]), Some(2));
+}
+
+#[test]
+fn crate_roots_named_rustc_are_analyzed() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("rustc.rs");
+    std::fs::write(&root, "pub fn div(x: u8, y: u8) -> u8 { x / y }").unwrap();
+    let status = Command::new(env!("CARGO_BIN_EXE_absint"))
+        .arg(&root)
+        .arg("--crate-type=lib")
+        .arg("--out-dir")
+        .arg(dir.path())
+        .status()
+        .unwrap();
+    // Run as the driver rather than as a wrapper around `rustc.rs`, so the alarm is reported
+    assert_eq!(status.code(), Some(1));
+}
+
+#[test]
+fn wrapper_mode_doesnt_depend_on_the_name_of_rustc() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("lib.rs");
+    std::fs::write(&root, "pub fn div(x: u8, y: u8) -> u8 { x / y }").unwrap();
+    let results_dir = dir.path().join("results");
+    std::fs::create_dir(&results_dir).unwrap();
+    // Cargo passes the path of the wrapped compiler first, here a toolchain-specific name. It is
+    // only run for queries, so it doesn't have to exist.
+    let status = Command::new(env!("CARGO_BIN_EXE_absint"))
+        .env("ABSINT_RESULTS_DIR", &results_dir)
+        .arg(dir.path().join("rustc-nightly-x86_64-unknown-linux-gnu"))
+        .arg(&root)
+        .args(["--crate-name", "demo", "--crate-type=lib", "--out-dir"])
+        .arg(dir.path())
+        .status()
+        .unwrap();
+    // Compilation continues in wrapper mode, and the alarm goes to the results file
+    assert_eq!(status.code(), Some(0));
+    let results = std::fs::read_to_string(results_dir.join("demo.txt")).unwrap();
+    assert!(results.contains("div: possible division by zero"), "{results}");
+}